@@ -0,0 +1,42 @@
+use crate::gamestate::{GameResult, GameState, Player, Side};
+
+/// The score of a game that is won. Every other score is a lot closer to 0
+pub const WIN_SCORE: i32 = 1_000_000;
+
+/// How much a single point of hero health is worth
+const HEALTH_WEIGHT: i32 = 1;
+
+/// How much a single point of attack of a minion on the field is worth
+const MINION_ATTACK_WEIGHT: i32 = 2;
+
+/// How much a single point of health of a minion on the field is worth
+const MINION_HEALTH_WEIGHT: i32 = 2;
+
+/// How much a single card in the hand is worth
+const HAND_CARD_WEIGHT: i32 = 2;
+
+/// Score the game from the point of view of `side`. A higher score is better for `side`
+/// A won game scores `WIN_SCORE`, a lost game scores `-WIN_SCORE` and a draw scores 0
+/// Otherwise the score compares the health of both heroes, the attack and health of their minions, and their hand sizes
+pub fn evaluate(state: &GameState, side: Side) -> i32 {
+    match state.result() {
+        Some(GameResult::Winner(winner)) if winner == side => WIN_SCORE,
+        Some(GameResult::Winner(_)) => -WIN_SCORE,
+        Some(GameResult::Draw) => 0,
+        None => player_score(state.player_at(side)) - player_score(state.player_at(side.other())),
+    }
+}
+
+/// Score the position of a single player
+fn player_score(player: &Player) -> i32 {
+    let field: i32 = player
+        .field
+        .iter()
+        .map(|instance| {
+            let attack = instance.card.attack().cloned().unwrap_or(0);
+            let health = instance.card.health().cloned().unwrap_or(0);
+            i32::from(attack) * MINION_ATTACK_WEIGHT + i32::from(health) * MINION_HEALTH_WEIGHT
+        })
+        .sum();
+    player.health * HEALTH_WEIGHT + field + player.hand.len() as i32 * HAND_CARD_WEIGHT
+}
@@ -0,0 +1,222 @@
+mod evaluation;
+mod search;
+
+pub use self::evaluation::{evaluate, WIN_SCORE};
+pub use self::search::{Search, SearchConfig, SearchResult};
+
+use crate::cards::ResourceType;
+use crate::gamestate::{Action, GameState, Phase, PlayerView, Side, Target};
+use std::{fmt, str::FromStr};
+
+/// How well the computer opponent plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Plays the first minion it can pay for and attacks the enemy hero with everything, but never casts spells
+    Easy,
+    /// Tries every action on a copy of the game, and takes the one that improves its position the most
+    Normal,
+    /// Like normal, but looks two actions ahead, so it finds combinations like removing a minion before attacking
+    Hard,
+    /// Searches every possible way the game can continue, including the turns of its opponent, see `Search`
+    Expert,
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            "expert" => Ok(Difficulty::Expert),
+            _ => Err(format!(
+                "Unknown difficulty {:?}, expected easy, normal, hard or expert",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Normal => write!(f, "normal"),
+            Difficulty::Hard => write!(f, "hard"),
+            Difficulty::Expert => write!(f, "expert"),
+        }
+    }
+}
+
+/// A computer player that plays the turns of one side of the board
+/// The AI picks one action at a time, which has to be applied with `GameState::apply` like any other action
+/// It only gets to see the game through a `PlayerView` of its side, so it can't look at the cards of its opponent
+pub struct Ai {
+    pub side: Side,
+    pub difficulty: Difficulty,
+
+    /// How deep and how long the expert difficulty searches
+    pub search: SearchConfig,
+}
+
+impl Ai {
+    pub fn new(side: Side, difficulty: Difficulty) -> Ai {
+        Ai {
+            side,
+            difficulty,
+            search: SearchConfig::default(),
+        }
+    }
+
+    /// Pick the next action that the AI wants to take
+    /// Returns None if the view is not of the AI's side, if it's not the turn of the AI's side, or if the game is over
+    pub fn choose_action(&self, view: PlayerView) -> Option<Action> {
+        if view.side() != self.side || view.turn().active != self.side || view.result().is_some() {
+            return None;
+        }
+        // Actions are tried out on a game that only holds what the AI knows
+        // The opponent's cards are hidden in this game, so the AI expects the opponent to only attack with their minions
+        let state = view.to_game_state();
+        let actions = state.legal_actions();
+        let action = match self.difficulty {
+            Difficulty::Expert => Search::new(self.search)
+                .find_best_action(&state)
+                .map(|result| result.action),
+            _ if state.turn.phase == Phase::StartOfTurn => {
+                Some(Action::SelectResource(self.choose_resource(&state)))
+            }
+            Difficulty::Easy => Ai::choose_easy_action(&actions),
+            Difficulty::Normal => self.choose_best_action(&state, &actions, 1),
+            Difficulty::Hard => self.choose_best_action(&state, &actions, 2),
+        };
+        Some(action.unwrap_or(Action::EndTurn))
+    }
+
+    /// Pick the resource that the cards in the AI's hand need the most
+    /// If no card needs more resources, the resource that the rest of the deck needs the most is picked
+    fn choose_resource(&self, state: &GameState) -> ResourceType {
+        let player = state.player_at(self.side);
+        let shortage = |resource: ResourceType| -> u32 {
+            let owned: u32 = player
+                .resources
+                .iter()
+                .filter(|(r, _)| *r == resource)
+                .map(|(_, amount)| u32::from(*amount))
+                .sum();
+            player
+                .hand
+                .iter()
+                .map(|instance| {
+                    let required: u32 = instance
+                        .card
+                        .cost()
+                        .iter()
+                        .filter(|(r, _)| *r == resource)
+                        .map(|(_, amount)| u32::from(*amount))
+                        .sum();
+                    required.saturating_sub(owned)
+                })
+                .max()
+                .unwrap_or(0)
+        };
+        let deck_demand = |resource: ResourceType| -> u32 {
+            player
+                .deck
+                .iter()
+                .flat_map(|card| card.cost())
+                .filter(|(r, _)| *r == resource)
+                .map(|(_, amount)| u32::from(amount))
+                .sum()
+        };
+        // max_by_key returns the last maximum, so iterate in reverse to prefer the first resource type on ties
+        ResourceType::ALL
+            .iter()
+            .rev()
+            .max_by_key(|resource| (shortage(**resource), deck_demand(**resource)))
+            .cloned()
+            .unwrap_or(ResourceType::Red)
+    }
+
+    /// Play the first minion that can be played, otherwise attack the opponent's hero with the first minion that can
+    fn choose_easy_action(actions: &[Action]) -> Option<Action> {
+        let play = actions
+            .iter()
+            .find(|action| matches!(action, Action::PlayCard { .. }));
+        let attack = actions.iter().find(|action| {
+            matches!(
+                action,
+                Action::Attack {
+                    target: Target::Hero(_),
+                    ..
+                }
+            )
+        });
+        play.or(attack).cloned()
+    }
+
+    /// Pick the action that improves the AI's position the most, looking `depth` actions ahead
+    /// Cards are played before attacking, because no cards can be played once combat starts
+    /// Returns None if no action improves the position, which means the turn should end
+    fn choose_best_action(
+        &self,
+        state: &GameState,
+        actions: &[Action],
+        depth: u32,
+    ) -> Option<Action> {
+        let current = evaluate(state, self.side);
+        let is_card =
+            |action: &&Action| matches!(action, Action::PlayCard { .. } | Action::CastSpell { .. });
+        let is_attack = |action: &&Action| matches!(action, Action::Attack { .. });
+
+        for candidates in &[
+            actions.iter().filter(is_card).cloned().collect::<Vec<_>>(),
+            actions
+                .iter()
+                .filter(is_attack)
+                .cloned()
+                .collect::<Vec<_>>(),
+        ] {
+            if let Some((action, score)) = self.best_of(state, candidates, depth) {
+                if score > current {
+                    return Some(action);
+                }
+            }
+        }
+        None
+    }
+
+    /// Try every action on a copy of the game, and return the action that leads to the highest score
+    /// If `depth` is more than 1, the best follow-up action of the same turn is taken into account as well
+    fn best_of(&self, state: &GameState, actions: &[Action], depth: u32) -> Option<(Action, i32)> {
+        let mut best: Option<(Action, i32)> = None;
+        for action in actions {
+            let mut next = state.clone();
+            if next.apply(next.turn.active, *action).is_err() {
+                continue;
+            }
+            let mut score = evaluate(&next, self.side);
+            if depth > 1 && next.turn.active == self.side && next.result().is_none() {
+                let follow_ups: Vec<Action> = next
+                    .legal_actions()
+                    .into_iter()
+                    .filter(|action| {
+                        matches!(
+                            action,
+                            Action::PlayCard { .. }
+                                | Action::CastSpell { .. }
+                                | Action::Attack { .. }
+                        )
+                    })
+                    .collect();
+                if let Some((_, follow_up_score)) = self.best_of(&next, &follow_ups, depth - 1) {
+                    score = score.max(follow_up_score);
+                }
+            }
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((*action, score));
+            }
+        }
+        best
+    }
+}
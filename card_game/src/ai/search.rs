@@ -0,0 +1,313 @@
+use super::{evaluate, WIN_SCORE};
+use crate::gamestate::{Action, GameState, Side, Target};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How much work a search is allowed to do
+#[derive(Debug, Clone, Copy)]
+pub struct SearchConfig {
+    /// The maximum number of actions to look ahead. Every action counts, not just every turn
+    pub max_depth: u32,
+
+    /// How long a search may take. The search stops at the first depth that is not done in time
+    /// The first depth is always searched completely, so a search can take a bit longer than this
+    pub time_budget: Duration,
+}
+
+impl Default for SearchConfig {
+    fn default() -> SearchConfig {
+        SearchConfig {
+            max_depth: 8,
+            time_budget: Duration::from_millis(250),
+        }
+    }
+}
+
+/// The outcome of a search
+#[derive(Debug, Clone, Copy)]
+pub struct SearchResult {
+    /// The best action that was found
+    pub action: Action,
+    /// The score of the game after playing the best action, as `evaluate` would score it for the searching side
+    pub score: i32,
+    /// The number of actions that were looked ahead
+    pub depth: u32,
+    /// The number of game states that were looked at
+    pub nodes: u64,
+}
+
+/// Finds the best action for a side with a minimax search with alpha-beta pruning
+/// The game has no randomness, so the search can look at every possible game
+/// The search only knows what the searching side can see, see `PlayerView::to_game_state`. It assumes that the hidden
+/// cards in the other player's hand can't be played, so it only expects them to attack with their minions
+/// The search deepens one action at a time until `SearchConfig::max_depth` is reached or the time runs out
+pub struct Search {
+    config: SearchConfig,
+
+    // The side that the search is looking for the best action for
+    side: Side,
+
+    // Scores of states that were already searched, keyed by `GameState::state_hash`
+    table: HashMap<u64, TableEntry>,
+
+    // Statistics and time keeping of the current search
+    nodes: u64,
+    deadline: Instant,
+    completed_depth: u32,
+}
+
+/// A state that was searched before
+#[derive(Clone, Copy)]
+struct TableEntry {
+    depth: u32,
+    score: i32,
+    bound: Bound,
+    best_action: Option<Action>,
+}
+
+/// How the score in a `TableEntry` relates to the real score of that state
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    /// The score is the real score
+    Exact,
+    /// The real score is at least the score
+    Lower,
+    /// The real score is at most the score
+    Upper,
+}
+
+/// Returned when the search runs out of time
+struct OutOfTime;
+
+/// How many states are looked at between checking the time
+const NODES_PER_TIME_CHECK: u64 = 256;
+
+/// Scores from this far away from 0 are wins or losses, which are lowered by the number of actions it takes to get there
+const DECIDED_SCORE: i32 = WIN_SCORE - 1000;
+
+impl Search {
+    pub fn new(config: SearchConfig) -> Search {
+        Search {
+            config,
+            side: Side::Player,
+            table: HashMap::new(),
+            nodes: 0,
+            deadline: Instant::now(),
+            completed_depth: 0,
+        }
+    }
+
+    /// Find the best action for the active side of the given game
+    /// Returns None if the game is over
+    pub fn find_best_action(&mut self, state: &GameState) -> Option<SearchResult> {
+        if state.result().is_some() {
+            return None;
+        }
+        self.side = state.turn.active;
+        self.table.clear();
+        self.nodes = 0;
+        self.deadline = Instant::now() + self.config.time_budget;
+        self.completed_depth = 0;
+
+        // Every state in the search is a copy of the game, and the original decks are only used to start a new game
+        // Leaving them out makes copying the game about twice as fast
+        let mut state = state.clone();
+        state.player.original_deck.clear();
+        state.opponent.original_deck.clear();
+
+        let root = state.state_hash();
+        let mut result = None;
+        for depth in 1..=self.config.max_depth.max(1) {
+            let score = match self.search(&state, depth, 0, -WIN_SCORE - 1, WIN_SCORE + 1) {
+                Ok(score) => score,
+                Err(OutOfTime) => break,
+            };
+            self.completed_depth = depth;
+            let action = match self.table.get(&root).and_then(|entry| entry.best_action) {
+                Some(action) => action,
+                None => break,
+            };
+            result = Some(SearchResult {
+                action,
+                score,
+                depth,
+                nodes: self.nodes,
+            });
+            // There is no need to look further ahead once the game is decided
+            if score.abs() >= DECIDED_SCORE {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Get the score of `state` for the searching side, looking `depth` actions ahead
+    /// `ply` is the number of actions since the start of the search, which is used to prefer faster wins and slower losses
+    fn search(
+        &mut self,
+        state: &GameState,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        mut beta: i32,
+    ) -> Result<i32, OutOfTime> {
+        self.nodes += 1;
+        if self.completed_depth > 0
+            && self.nodes.is_multiple_of(NODES_PER_TIME_CHECK)
+            && Instant::now() >= self.deadline
+        {
+            return Err(OutOfTime);
+        }
+        if state.result().is_some() || depth == 0 {
+            return Ok(from_win_distance(evaluate(state, self.side), ply));
+        }
+
+        let hash = state.state_hash();
+        let mut table_action = None;
+        if let Some(entry) = self.table.get(&hash) {
+            if entry.depth >= depth {
+                let score = from_win_distance(entry.score, ply);
+                match entry.bound {
+                    Bound::Exact => return Ok(score),
+                    Bound::Lower => alpha = alpha.max(score),
+                    Bound::Upper => beta = beta.min(score),
+                }
+                if alpha >= beta {
+                    return Ok(score);
+                }
+            }
+            table_action = entry.best_action;
+        }
+
+        let maximizing = state.turn.active == self.side;
+        let (original_alpha, original_beta) = (alpha, beta);
+        let mut best: Option<(Action, i32)> = None;
+        for action in ordered_actions(state, table_action) {
+            let mut next = state.clone();
+            if next.apply(next.turn.active, action).is_err() {
+                continue;
+            }
+            let score = self.search(&next, depth - 1, ply + 1, alpha, beta)?;
+            let is_better = best.is_none_or(|(_, best_score)| {
+                if maximizing {
+                    score > best_score
+                } else {
+                    score < best_score
+                }
+            });
+            if is_better {
+                best = Some((action, score));
+            }
+            if maximizing {
+                alpha = alpha.max(score);
+            } else {
+                beta = beta.min(score);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let (best_action, best_score) = match best {
+            Some(best) => best,
+            None => return Ok(from_win_distance(evaluate(state, self.side), ply)),
+        };
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= original_beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.insert(
+            hash,
+            TableEntry {
+                depth,
+                score: to_win_distance(best_score, ply),
+                bound,
+                best_action: Some(best_action),
+            },
+        );
+        Ok(best_score)
+    }
+}
+
+/// Get the legal actions of `state`, with the actions that are most likely to be good first
+/// Alpha-beta pruning skips more of the search when good actions are tried first
+/// The best action of an earlier search of this state goes first, then spells, minions, attacks on heroes and attacks on minions
+/// Advancing the phase is left out, because it only takes options away from the active player
+fn ordered_actions(state: &GameState, first: Option<Action>) -> Vec<Action> {
+    let mut actions: Vec<Action> = state
+        .legal_actions()
+        .into_iter()
+        .filter(|action| *action != Action::AdvancePhase)
+        .collect();
+    actions.sort_by_key(|action| match action {
+        _ if Some(*action) == first => 0,
+        Action::CastSpell { .. } => 1,
+        Action::PlayCard { .. } => 2,
+        Action::Attack {
+            target: Target::Hero(_),
+            ..
+        } => 3,
+        Action::Attack { .. } => 4,
+        Action::SelectResource(_) => 5,
+        Action::AdvancePhase | Action::EndTurn => 6,
+    });
+    actions
+}
+
+/// Turn a win or loss at `ply` actions from the start of the search into a win or loss at `ply` actions from now
+/// This makes scores in the table independent of where in the search the state was found
+fn to_win_distance(score: i32, ply: u32) -> i32 {
+    if score >= DECIDED_SCORE {
+        score + ply as i32
+    } else if score <= -DECIDED_SCORE {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+/// Turn a score from `evaluate` or the table into a score from the start of the search
+/// Wins that take more actions score a bit lower, and losses that take more actions score a bit higher
+fn from_win_distance(score: i32, ply: u32) -> i32 {
+    if score >= DECIDED_SCORE {
+        score - ply as i32
+    } else if score <= -DECIDED_SCORE {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Search, SearchConfig, DECIDED_SCORE};
+    use crate::cards::ResourceType;
+    use crate::gamestate::{GameResult, Side};
+    use crate::test_utils::{new_game, put_minion, skip_to_main};
+    use std::time::Duration;
+
+    #[test]
+    fn search_finds_a_win_in_one_turn() {
+        let mut game_state = new_game();
+        skip_to_main(&mut game_state);
+        // The minion deals 5 damage and the damage spell in the hand 3, which is exactly enough
+        put_minion(&mut game_state, Side::Player, "generic_minion");
+        game_state.player.available_resources = vec![(ResourceType::Red, 2)];
+        game_state.opponent.health = 8;
+
+        let mut search = Search::new(SearchConfig {
+            max_depth: 4,
+            time_budget: Duration::from_secs(10),
+        });
+        while game_state.result().is_none() {
+            assert_eq!(game_state.turn.active, Side::Player);
+            let result = search.find_best_action(&game_state).unwrap();
+            assert!(result.score >= DECIDED_SCORE);
+            game_state.apply(Side::Player, result.action).unwrap();
+        }
+        assert_eq!(game_state.result(), Some(GameResult::Winner(Side::Player)));
+    }
+}
@@ -0,0 +1,13 @@
+//! Reading the command line arguments of the game client and the server
+
+/// Get the value of a command line argument, e.g. `--deck <file>`
+/// Returns None if the argument is not given, or an error if the argument is given without a value
+pub fn get_argument<'a>(args: &'a [String], name: &str) -> Result<Option<&'a str>, String> {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) => args
+            .get(index + 1)
+            .map(|value| Some(value.as_str()))
+            .ok_or_else(|| format!("{} needs a value", name)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,39 @@
+use crate::cards::Card;
+
+/// A single card in a game, together with the state that the card has built up during that game
+/// This holds no information about how the card is shown on the screen
+#[derive(Clone)]
+pub struct CardInstance {
+    /// Identifies this card for the rest of the game
+    /// Every card gets an id when it's drawn, which is unique for the player that owns it
+    pub id: u32,
+
+    /// The card itself, including its current stats
+    pub card: Box<dyn Card>,
+
+    /// Is this minion summoned this turn? Minions can not attack on the turn that they're summoned
+    pub summoning_sick: bool,
+
+    /// Did this minion attack this turn? Minions can only attack once per turn
+    pub has_attacked: bool,
+}
+
+impl CardInstance {
+    /// Create a new instance of the given card with the given id
+    pub fn new(id: u32, card: Box<dyn Card>) -> CardInstance {
+        CardInstance {
+            id,
+            card,
+            summoning_sick: true,
+            has_attacked: false,
+        }
+    }
+
+    /// Returns true if this card is a living minion that is still allowed to attack this turn
+    pub fn can_attack(&self) -> bool {
+        self.card.attack().is_some()
+            && self.card.health().is_some_and(|health| *health > 0)
+            && !self.summoning_sick
+            && !self.has_attacked
+    }
+}
@@ -0,0 +1,158 @@
+use super::{
+    Ability, Card, CardDatabase, CardDefinition, CardKind, CardPlayEffect, Effect,
+    EffectDefinition, ResourceType,
+};
+use crate::gamestate::{GameState, Side, Target};
+use std::sync::Arc;
+
+/// A card that is described by a `CardDefinition` from the card database
+/// The definition is shared between all copies of the card, only the current attack and health are stored per card
+#[derive(Clone)]
+pub struct DataCard {
+    definition: Arc<CardDefinition>,
+
+    // The database that this card came from, used to create the minions that this card summons
+    database: CardDatabase,
+
+    // The current attack and health of the card. These are 0 for spells
+    attack: u8,
+    health: u8,
+}
+
+impl DataCard {
+    /// Create a new card based on the given definition, with the stats from the definition
+    pub fn new(definition: Arc<CardDefinition>, database: CardDatabase) -> DataCard {
+        let (attack, health) = match definition.kind {
+            CardKind::Minion { attack, health } => (attack, health),
+            CardKind::Spell { .. } => (0, 0),
+        };
+        DataCard {
+            definition,
+            database,
+            attack,
+            health,
+        }
+    }
+
+    /// Get the definition of this card
+    pub fn definition(&self) -> &CardDefinition {
+        &self.definition
+    }
+
+    fn is_minion(&self) -> bool {
+        matches!(self.definition.kind, CardKind::Minion { .. })
+    }
+
+    fn has_ability(&self, ability: Ability) -> bool {
+        self.definition.abilities.contains(&ability)
+    }
+}
+
+impl Card for DataCard {
+    fn id(&self) -> &str {
+        &self.definition.id
+    }
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+    fn cost(&self) -> Vec<(ResourceType, u8)> {
+        self.definition.cost.clone()
+    }
+    fn description(&self) -> &str {
+        &self.definition.description
+    }
+    fn art(&self) -> Option<&str> {
+        self.definition.art.as_deref()
+    }
+    fn attack(&self) -> Option<&u8> {
+        if !self.is_minion() {
+            None
+        } else if self.has_ability(Ability::AttackEqualsHealth) {
+            Some(&self.health)
+        } else {
+            Some(&self.attack)
+        }
+    }
+    fn health(&self) -> Option<&u8> {
+        if self.is_minion() {
+            Some(&self.health)
+        } else {
+            None
+        }
+    }
+    fn max_health(&self) -> Option<u8> {
+        match self.definition.kind {
+            CardKind::Minion { health, .. } => Some(health),
+            CardKind::Spell { .. } => None,
+        }
+    }
+    fn health_mut(&mut self) -> Option<&mut u8> {
+        if self.is_minion() {
+            Some(&mut self.health)
+        } else {
+            None
+        }
+    }
+    fn attack_mut(&mut self) -> Option<&mut u8> {
+        // the attack of a minion with AttackEqualsHealth can only change through its health
+        if self.is_minion() && !self.has_ability(Ability::AttackEqualsHealth) {
+            Some(&mut self.attack)
+        } else {
+            None
+        }
+    }
+    fn play_effects(&self) -> Vec<CardPlayEffect> {
+        match self.definition.kind {
+            CardKind::Minion { .. } => vec![CardPlayEffect::SummonMinion],
+            CardKind::Spell { .. } => vec![CardPlayEffect::Target(self.definition.target_type())],
+        }
+    }
+    fn on_play(&self, caster: Side, target: Option<Target>, _state: &GameState) -> Vec<Effect> {
+        self.definition
+            .effects
+            .iter()
+            .filter_map(|effect| match *effect {
+                EffectDefinition::Damage {
+                    target: effect_target,
+                    amount,
+                } => effect_target
+                    .resolve(caster, target)
+                    .map(|target| Effect::Damage { target, amount }),
+                EffectDefinition::Heal {
+                    target: effect_target,
+                    amount,
+                } => effect_target
+                    .resolve(caster, target)
+                    .map(|target| Effect::Heal { target, amount }),
+                EffectDefinition::Buff {
+                    target: effect_target,
+                    attack,
+                    health,
+                } => effect_target
+                    .resolve(caster, target)
+                    .map(|target| Effect::Buff {
+                        target,
+                        attack,
+                        health,
+                    }),
+                EffectDefinition::Draw { amount } => Some(Effect::Draw {
+                    side: caster,
+                    amount,
+                }),
+                EffectDefinition::Summon { ref card } => self
+                    .database
+                    .create_card(card)
+                    .map(|card| Effect::Summon { side: caster, card }),
+            })
+            .collect()
+    }
+    fn debug_text(&self) -> String {
+        match (self.attack(), self.health()) {
+            (Some(attack), Some(health)) => format!("{} ({}/{})", self.name(), attack, health),
+            _ => self.name().to_string(),
+        }
+    }
+    fn clone_box(&self) -> Box<dyn Card> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,213 @@
+use super::{Card, CardDefinition, CardKind, DataCard, EffectDefinition};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::{fmt, fs, io, path::Path};
+
+/// All the cards that exist in the game
+/// The cards are described in a RON file, see `assets/cards.ron`, so new cards can be added without recompiling the game
+/// Cloning a database is cheap, all clones share the same definitions
+#[derive(Clone, Default)]
+pub struct CardDatabase {
+    definitions: Arc<HashMap<String, Arc<CardDefinition>>>,
+}
+
+impl CardDatabase {
+    /// Load the card database from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<CardDatabase, CardDatabaseError> {
+        let contents = fs::read_to_string(path).map_err(CardDatabaseError::Io)?;
+        CardDatabase::parse(&contents)
+    }
+
+    /// Parse a card database from the contents of a card database file
+    /// Every card is checked, and the first card that is not valid is returned as an error
+    pub fn parse(contents: &str) -> Result<CardDatabase, CardDatabaseError> {
+        let list: Vec<CardDefinition> =
+            ron::from_str(contents).map_err(|e| CardDatabaseError::Format(e.to_string()))?;
+        // The cards are checked in the order of the file, so the same card is reported every time
+        let mut ids = HashSet::new();
+        for definition in &list {
+            if !ids.insert(definition.id.as_str()) {
+                return Err(CardDatabaseError::DuplicateId(definition.id.clone()));
+            }
+            CardDatabase::check_definition(definition, &list)?;
+        }
+        let definitions = list
+            .into_iter()
+            .map(|definition| (definition.id.clone(), Arc::new(definition)))
+            .collect();
+        Ok(CardDatabase {
+            definitions: Arc::new(definitions),
+        })
+    }
+
+    /// Check that a card can actually be played
+    fn check_definition(
+        definition: &CardDefinition,
+        definitions: &[CardDefinition],
+    ) -> Result<(), CardDatabaseError> {
+        let invalid = |reason: &str| {
+            Err(CardDatabaseError::InvalidCard {
+                id: definition.id.clone(),
+                reason: reason.to_string(),
+            })
+        };
+        let is_spell = match &definition.kind {
+            CardKind::Minion { .. } => false,
+            CardKind::Spell { targets } if targets.is_empty() => {
+                return invalid("a spell needs at least one kind of target")
+            }
+            CardKind::Spell { .. } => true,
+        };
+        for effect in &definition.effects {
+            if effect.uses_target() && !is_spell {
+                return invalid("only spells have a target that effects can use");
+            }
+            if let EffectDefinition::Summon { card } = effect {
+                let summoned = definitions.iter().find(|summoned| summoned.id == *card);
+                match summoned.map(|summoned| &summoned.kind) {
+                    Some(CardKind::Minion { .. }) => {}
+                    Some(CardKind::Spell { .. }) => {
+                        return invalid(&format!("summoned card {:?} is not a minion", card))
+                    }
+                    None => return invalid(&format!("summoned card {:?} does not exist", card)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the definition of the card with the given id
+    pub fn get(&self, id: &str) -> Option<&Arc<CardDefinition>> {
+        self.definitions.get(id)
+    }
+
+    /// Create a new card based on its id
+    /// Returns None if there is no card with the given id
+    pub fn create_card(&self, id: &str) -> Option<Box<dyn Card>> {
+        self.get(id).map(|definition| {
+            Box::new(DataCard::new(definition.clone(), self.clone())) as Box<dyn Card>
+        })
+    }
+
+    /// Create a new card based on its id, with the given attack and health instead of the ones from its definition
+    /// Stats that are None or that the card doesn't have are left as they are
+    /// Returns None if there is no card with the given id
+    pub fn create_card_with_stats(
+        &self,
+        id: &str,
+        attack: Option<u8>,
+        health: Option<u8>,
+    ) -> Option<Box<dyn Card>> {
+        let mut card = self.create_card(id)?;
+        if let (Some(attack), Some(card_attack)) = (attack, card.attack_mut()) {
+            *card_attack = attack;
+        }
+        if let (Some(health), Some(card_health)) = (health, card.health_mut()) {
+            *card_health = health;
+        }
+        Some(card)
+    }
+
+    /// Get the ids of all the cards in the database, in alphabetical order
+    pub fn ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.definitions.keys().map(|id| id.as_str()).collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+/// The reasons why a card database can not be loaded
+#[derive(Debug)]
+pub enum CardDatabaseError {
+    /// The card database file could not be read
+    Io(io::Error),
+    /// The card database file is not a valid list of cards
+    Format(String),
+    /// There are multiple cards with the same id
+    DuplicateId(String),
+    /// The card with the given id can not be played
+    InvalidCard { id: String, reason: String },
+}
+
+impl fmt::Display for CardDatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardDatabaseError::Io(e) => write!(f, "Could not read the card database: {}", e),
+            CardDatabaseError::Format(e) => write!(f, "Invalid card database: {}", e),
+            CardDatabaseError::DuplicateId(id) => {
+                write!(f, "There are multiple cards with id {:?}", id)
+            }
+            CardDatabaseError::InvalidCard { id, reason } => {
+                write!(f, "Card {:?} is not valid: {}", id, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CardDatabaseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardDatabase, CardDatabaseError};
+
+    /// Parse a card database with the given cards, and return the id of the card that is rejected
+    fn rejected_card(cards: &str) -> String {
+        match CardDatabase::parse(&format!("[{}]", cards)) {
+            Ok(_) => panic!("the cards were not rejected"),
+            Err(CardDatabaseError::InvalidCard { id, .. }) => id,
+            Err(CardDatabaseError::DuplicateId(id)) => format!("duplicate {}", id),
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    const MINION: &str =
+        r#"(id: "minion", name: "Minion", cost: [], kind: Minion(attack: 1, health: 1)),"#;
+
+    #[test]
+    fn cards_that_can_not_be_played_are_rejected() {
+        let spell_without_targets =
+            r#"(id: "spell", name: "Spell", cost: [], kind: Spell(targets: []))"#;
+        assert_eq!(rejected_card(spell_without_targets), "spell");
+
+        let minion_with_target = r#"(id: "bad", name: "Bad", cost: [], kind: Minion(attack: 1, health: 1),
+            effects: [Damage(target: Target, amount: 1)])"#;
+        assert_eq!(rejected_card(minion_with_target), "bad");
+
+        let summons_nothing = r#"(id: "summoner", name: "Summoner", cost: [], kind: Minion(attack: 1, health: 1),
+            effects: [Summon(card: "missing")])"#;
+        assert_eq!(rejected_card(summons_nothing), "summoner");
+
+        let summons_spell = r#"(id: "summoner", name: "Summoner", cost: [], kind: Minion(attack: 1, health: 1),
+            effects: [Summon(card: "spell")]),
+            (id: "spell", name: "Spell", cost: [], kind: Spell(targets: [OwnHero]))"#;
+        assert_eq!(rejected_card(summons_spell), "summoner");
+
+        assert_eq!(
+            rejected_card(&format!("{}{}", MINION, MINION)),
+            "duplicate minion"
+        );
+    }
+
+    #[test]
+    fn first_invalid_card_in_the_file_is_reported() {
+        let cards: String = (0..20)
+            .map(|index| {
+                format!(
+                    r#"(id: "spell_{}", name: "Spell", cost: [], kind: Spell(targets: [])),"#,
+                    index
+                )
+            })
+            .collect();
+        for _ in 0..10 {
+            assert_eq!(rejected_card(&cards), "spell_0");
+        }
+    }
+
+    #[test]
+    fn minions_can_summon_cards_that_come_later_in_the_file() {
+        let summoner = r#"(id: "summoner", name: "Summoner", cost: [], kind: Minion(attack: 1, health: 1),
+            effects: [Summon(card: "minion")]),"#;
+        let database = CardDatabase::parse(&format!("[{}{}]", summoner, MINION)).unwrap();
+        assert_eq!(database.ids(), vec!["minion", "summoner"]);
+    }
+}
@@ -0,0 +1,141 @@
+use super::{ResourceType, TargetType};
+use crate::gamestate::{Side, Target};
+use serde::{Deserialize, Serialize};
+
+/// The description of a single card, as it's written in the card database
+/// See `CardDatabase` for how these are loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardDefinition {
+    /// The unique id of the card, used to refer to this card from decks and replays
+    pub id: String,
+
+    /// The name of the card as it's shown to the player
+    pub name: String,
+
+    /// The text on the card, if any. This can contain newlines
+    #[serde(default)]
+    pub description: String,
+
+    /// The resources that need to be paid to play this card
+    pub cost: Vec<(ResourceType, u8)>,
+
+    /// If this card is a minion or a spell
+    pub kind: CardKind,
+
+    /// Special rules that apply to this card
+    #[serde(default)]
+    pub abilities: Vec<Ability>,
+
+    /// The effects that this card has when it's played, in the order that they get resolved
+    #[serde(default)]
+    pub effects: Vec<EffectDefinition>,
+
+    /// The path of the image that is shown on the card, relative to the assets directory
+    /// Cards without art show a placeholder
+    #[serde(default)]
+    pub art: Option<String>,
+}
+
+impl CardDefinition {
+    /// Get the TargetType flags that this card can target
+    /// This is empty for minions
+    pub fn target_type(&self) -> TargetType {
+        match &self.kind {
+            CardKind::Minion { .. } => TargetType::empty(),
+            CardKind::Spell { targets } => targets
+                .iter()
+                .fold(TargetType::empty(), |flags, target| flags | target.flag()),
+        }
+    }
+}
+
+/// The kind of card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CardKind {
+    /// A minion that gets put on the field when it's played
+    Minion { attack: u8, health: u8 },
+    /// A spell that is cast on one of the given kinds of targets, and then goes to the graveyard
+    Spell { targets: Vec<TargetKind> },
+}
+
+/// The kinds of things a spell can be cast on. See `TargetType`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetKind {
+    OwnHero,
+    OpponentHero,
+    OwnMinion,
+    OpponentMinion,
+}
+
+impl TargetKind {
+    /// Get the TargetType flag for this kind of target
+    pub fn flag(self) -> TargetType {
+        match self {
+            TargetKind::OwnHero => TargetType::TARGET_SELF,
+            TargetKind::OpponentHero => TargetType::TARGET_OPPONENT,
+            TargetKind::OwnMinion => TargetType::TARGET_OWNMINION,
+            TargetKind::OpponentMinion => TargetType::TARGET_OPPONENTMINION,
+        }
+    }
+}
+
+/// Special rules that a card can have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Ability {
+    /// The attack of this minion is always equal to its health
+    AttackEqualsHealth,
+}
+
+/// An effect that a card has when it's played. See `Effect`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EffectDefinition {
+    /// Deal damage to a minion or hero
+    Damage { target: EffectTarget, amount: u8 },
+    /// Restore health of a minion or hero
+    Heal { target: EffectTarget, amount: u8 },
+    /// Give a minion extra attack and health
+    Buff {
+        target: EffectTarget,
+        attack: u8,
+        health: u8,
+    },
+    /// The player that played the card draws cards from their deck
+    Draw { amount: u8 },
+    /// Put a new minion with the given card id on the field of the player that played the card
+    Summon { card: String },
+}
+
+impl EffectDefinition {
+    /// Returns true if this effect needs the target that the player picked when casting the card
+    pub fn uses_target(&self) -> bool {
+        match self {
+            EffectDefinition::Damage { target, .. }
+            | EffectDefinition::Heal { target, .. }
+            | EffectDefinition::Buff { target, .. } => *target == EffectTarget::Target,
+            EffectDefinition::Draw { .. } | EffectDefinition::Summon { .. } => false,
+        }
+    }
+}
+
+/// What an effect is applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectTarget {
+    /// The target that the player picked when casting the spell
+    Target,
+    /// The hero of the player that played the card
+    OwnHero,
+    /// The hero of the other player
+    OpponentHero,
+}
+
+impl EffectTarget {
+    /// Get the actual target of the effect, for a card played by `caster` on `target`
+    /// Returns None if the effect uses the picked target, but the card was played without a target
+    pub fn resolve(self, caster: Side, target: Option<Target>) -> Option<Target> {
+        match self {
+            EffectTarget::Target => target,
+            EffectTarget::OwnHero => Some(Target::Hero(caster)),
+            EffectTarget::OpponentHero => Some(Target::Hero(caster.other())),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use super::Card;
+use crate::gamestate::{Side, Target};
+
+/// A single change to the board that a card can cause when it gets played
+/// Cards return a list of these from `Card::on_play`, and the game resolves them in order
+pub enum Effect {
+    /// Deal damage to a minion or hero
+    Damage { target: Target, amount: u8 },
+    /// Restore health of a minion or hero. Heroes can not go above their starting health
+    Heal { target: Target, amount: u8 },
+    /// Give a minion extra attack and health
+    Buff {
+        target: Target,
+        attack: u8,
+        health: u8,
+    },
+    /// Let the player at the given side draw cards from their deck
+    Draw { side: Side, amount: u8 },
+    /// Put a new minion on the field of the given side
+    Summon { side: Side, card: Box<dyn Card> },
+}
@@ -0,0 +1,30 @@
+use super::{Card, CardPlayEffect, ResourceType};
+
+/// A card that the player is not allowed to see, like a card in the hand or the deck of the opponent
+/// This takes the place of the real card in a game that was created from a `Snapshot`, so the game still knows how
+/// many cards there are. A hidden card can not be played
+#[derive(Clone, Default)]
+pub struct HiddenCard;
+
+impl HiddenCard {
+    /// The id of every hidden card, which is never the id of a card in the card database
+    pub const ID: &'static str = "hidden";
+}
+
+impl Card for HiddenCard {
+    fn id(&self) -> &str {
+        HiddenCard::ID
+    }
+    fn name(&self) -> &str {
+        "Hidden card"
+    }
+    fn cost(&self) -> Vec<(ResourceType, u8)> {
+        Vec::new()
+    }
+    fn play_effects(&self) -> Vec<CardPlayEffect> {
+        Vec::new()
+    }
+    fn clone_box(&self) -> Box<dyn Card> {
+        Box::new(self.clone())
+    }
+}
@@ -1,19 +1,27 @@
-pub use std::rc::{Rc, Weak};
+mod data_card;
+mod database;
+mod definition;
+mod effect;
+mod hidden_card;
 
-mod buff_card;
-mod generic_minion;
-mod generic_spell;
-mod light_elemental;
+pub use self::data_card::DataCard;
+pub use self::database::{CardDatabase, CardDatabaseError};
+pub use self::definition::{
+    Ability, CardDefinition, CardKind, EffectDefinition, EffectTarget, TargetKind,
+};
+pub use self::effect::Effect;
+pub use self::hidden_card::HiddenCard;
 
-pub use self::buff_card::*;
-pub use self::generic_minion::*;
-pub use self::generic_spell::*;
-pub use self::light_elemental::*;
+use crate::gamestate::{GameState, Side, Target};
+use serde::{Deserialize, Serialize};
 
 /// The basic card trait
 /// This is the "contract" that binds card implementations to the system
-/// Only name() and cost() are required, the others can be overwritten when needed
+/// Only id(), name() and cost() are required, the others can be overwritten when needed
 pub trait Card {
+    /// Get the id of the card
+    /// This is used to store the card in files, like replays. See `CardDatabase`
+    fn id(&self) -> &str;
     /// Get the name of the card
     fn name(&self) -> &str;
     /// Get the cost of the card
@@ -22,6 +30,10 @@ pub trait Card {
     fn description(&self) -> &str {
         ""
     }
+    /// Get the path of the image that is shown on the card, relative to the assets directory
+    fn art(&self) -> Option<&str> {
+        None
+    }
     /// Get the attack of the card, if any
     /// Minions should always have an attack value
     fn attack(&self) -> Option<&u8> {
@@ -33,16 +45,32 @@ pub trait Card {
     fn health(&self) -> Option<&u8> {
         None
     }
+    /// Get the health that the card has before it takes any damage, if any
+    /// Healing a minion doesn't raise its health above this
+    fn max_health(&self) -> Option<u8> {
+        None
+    }
     /// Mutable reference to the health
     /// See health(&self) for more info
     fn health_mut(&mut self) -> Option<&mut u8> {
         None
     }
+    /// Mutable reference to the attack
+    /// Cards whose attack is derived from other stats can leave this as None
+    fn attack_mut(&mut self) -> Option<&mut u8> {
+        None
+    }
     /// The effects that this card has when you try to play it from hand
     /// This can either be summoning a minion, or targetting a minion or player, or a combination of this
     fn play_effects(&self) -> Vec<CardPlayEffect> {
         vec![CardPlayEffect::SummonMinion]
     }
+    /// The effects that this card has on the board when it's played by `caster`
+    /// `target` is the target that the player picked for a `CardPlayEffect::Target` card, and None for minions
+    /// The returned effects get resolved by the game in order, so cards never have to change the `GameState` themselves
+    fn on_play(&self, _caster: Side, _target: Option<Target>, _state: &GameState) -> Vec<Effect> {
+        Vec::new()
+    }
     /// A debug text that's used to describe this card for logging purposes
     fn debug_text(&self) -> String {
         self.name().to_string()
@@ -51,13 +79,18 @@ pub trait Card {
     fn clone_box(&self) -> Box<dyn Card>;
 }
 
+impl Clone for Box<dyn Card> {
+    fn clone(&self) -> Box<dyn Card> {
+        self.clone_box()
+    }
+}
+
 /// The resource type that the game has
 /// Cards will require a certain amount of resources played. See Card::cost
 /// Players will be able to add 1 ResourceType to their pool every turn
 /// Cards will consume these resources, and they'll recharge the next turn
 /// Leaving the player with the same amount of total resources as they've had turns
-#[derive(Debug, Clone, Copy)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ResourceType {
     Red,
     Blue,
@@ -65,6 +98,16 @@ pub enum ResourceType {
     Black,
 }
 
+impl ResourceType {
+    /// All the resource types, in the order that they are shown to the player
+    pub const ALL: [ResourceType; 4] = [
+        ResourceType::Red,
+        ResourceType::Blue,
+        ResourceType::White,
+        ResourceType::Black,
+    ];
+}
+
 /// The effect that a card has when it gets played
 #[derive(Debug)]
 pub enum CardPlayEffect {
@@ -0,0 +1,17 @@
+/// The amount of cards that each player draws at the start of the game
+pub const STARTING_HAND_SIZE: usize = 5;
+
+/// The maximum amount of minions that a player can have on the field
+pub const MAX_FIELD_SIZE: usize = 7;
+
+/// The amount of health that each player starts the game with
+pub const STARTING_HEALTH: i32 = 100;
+
+/// The minimum amount of cards in a deck in the standard format
+pub const MIN_DECK_SIZE: usize = 40;
+
+/// The maximum amount of cards in a deck in the standard format
+pub const MAX_DECK_SIZE: usize = 60;
+
+/// The maximum amount of copies of a single card in a deck in the standard format
+pub const MAX_COPIES_PER_CARD: usize = 15;
@@ -0,0 +1,188 @@
+mod validator;
+
+pub use self::validator::{DeckFormat, DeckValidator, DeckViolation};
+
+use crate::cards::{Card, CardDatabase};
+use std::{fmt, fs, io, path::Path};
+
+/// An ordered list of cards that a player starts the game with
+/// The deck is never shuffled, so the cards are drawn in this order
+///
+/// A deck file has one card per line, optionally with the amount of copies in front of it:
+/// ```text
+/// # Lines starting with a # are comments
+/// 3 generic_minion
+/// light_elemental
+/// ```
+/// The copies of a card are put in the deck right after each other, at the position of their line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deck {
+    /// The ids of the cards in the deck, in the order that they are drawn. See `Card::id`
+    pub cards: Vec<String>,
+}
+
+impl Deck {
+    /// Load a deck from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Deck, DeckError> {
+        let contents = fs::read_to_string(path).map_err(DeckError::Io)?;
+        Deck::parse(&contents)
+    }
+
+    /// Parse a deck from the contents of a deck file
+    pub fn parse(contents: &str) -> Result<Deck, DeckError> {
+        let mut cards = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (count, id) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(id), None, None) => (1, id),
+                (Some(count), Some(id), None) => {
+                    let count = count.parse::<usize>().map_err(|_| DeckError::Parse {
+                        line: index + 1,
+                        reason: format!("{:?} is not a valid amount of cards", count),
+                    })?;
+                    (count, id)
+                }
+                _ => {
+                    return Err(DeckError::Parse {
+                        line: index + 1,
+                        reason: String::from("expected an amount and a card id"),
+                    })
+                }
+            };
+            cards.extend(std::iter::repeat_n(id.to_string(), count));
+        }
+        Ok(Deck { cards })
+    }
+
+    /// Create a deck from a list of cards, e.g. from `Player::original_deck`
+    pub fn from_cards(cards: &[Box<dyn Card>]) -> Deck {
+        Deck {
+            cards: cards.iter().map(|card| card.id().to_string()).collect(),
+        }
+    }
+
+    /// Create the cards of this deck from the given card database, in order
+    /// Returns an error if one of the cards does not exist
+    pub fn create_cards(&self, database: &CardDatabase) -> Result<Vec<Box<dyn Card>>, DeckError> {
+        self.cards
+            .iter()
+            .map(|id| {
+                database
+                    .create_card(id)
+                    .ok_or_else(|| DeckError::UnknownCard(id.clone()))
+            })
+            .collect()
+    }
+
+    /// Create the cards of this deck from the given card database, and check them against the validator
+    /// Returns an error if one of the cards does not exist, or if the deck breaks the rules of the validator's format
+    pub fn create_valid_cards(
+        &self,
+        database: &CardDatabase,
+        validator: &DeckValidator,
+    ) -> Result<Vec<Box<dyn Card>>, DeckError> {
+        let cards = self.create_cards(database)?;
+        validator.validate(&cards).map_err(DeckError::Invalid)?;
+        Ok(cards)
+    }
+
+    /// Save the deck to the given file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), DeckError> {
+        fs::write(path, self.to_string()).map_err(DeckError::Io)
+    }
+}
+
+impl fmt::Display for Deck {
+    /// Write the deck in the deck file format
+    /// Copies of a card that are right after each other are combined into a single line
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut index = 0;
+        while index < self.cards.len() {
+            let id = &self.cards[index];
+            let count = self.cards[index..]
+                .iter()
+                .take_while(|other| *other == id)
+                .count();
+            writeln!(f, "{} {}", count, id)?;
+            index += count;
+        }
+        Ok(())
+    }
+}
+
+/// The reasons why a deck can not be loaded
+#[derive(Debug)]
+pub enum DeckError {
+    /// The deck file could not be read or written
+    Io(io::Error),
+    /// The given line of the deck file is not valid
+    Parse { line: usize, reason: String },
+    /// The deck contains a card id that is not in the card database
+    UnknownCard(String),
+    /// The deck breaks the rules of the format
+    Invalid(Vec<DeckViolation>),
+}
+
+impl fmt::Display for DeckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeckError::Io(e) => write!(f, "Could not access the deck file: {}", e),
+            DeckError::Parse { line, reason } => write!(f, "Line {}: {}", line, reason),
+            DeckError::UnknownCard(id) => write!(f, "Unknown card {:?}", id),
+            DeckError::Invalid(violations) => {
+                write!(f, "The deck is not valid:")?;
+                for violation in violations {
+                    write!(f, "\n- {}", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Deck, DeckError};
+    use crate::test_utils::starter_deck;
+
+    #[test]
+    fn written_deck_is_parsed_as_the_same_deck() {
+        let deck = starter_deck();
+        assert_eq!(Deck::parse(&deck.to_string()).unwrap(), deck);
+
+        let deck =
+            Deck::parse("# A comment\n\n2 generic_minion\nlight_elemental\n 1  generic_minion \n")
+                .unwrap();
+        assert_eq!(
+            deck.cards,
+            vec![
+                "generic_minion",
+                "generic_minion",
+                "light_elemental",
+                "generic_minion"
+            ]
+        );
+        // Copies that are right after each other end up on the same line
+        assert_eq!(
+            deck.to_string(),
+            "2 generic_minion\n1 light_elemental\n1 generic_minion\n"
+        );
+        assert_eq!(Deck::parse(&deck.to_string()).unwrap(), deck);
+    }
+
+    #[test]
+    fn invalid_lines_are_reported_with_their_line_number() {
+        let invalid_line = |contents: &str| match Deck::parse(contents) {
+            Err(DeckError::Parse { line, .. }) => line,
+            result => panic!("unexpected result {:?}", result),
+        };
+        assert_eq!(invalid_line("generic_minion\nmany generic_minion"), 2);
+        assert_eq!(invalid_line("# Comment\n\n2 generic_minion extra"), 3);
+    }
+}
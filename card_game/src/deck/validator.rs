@@ -0,0 +1,243 @@
+use super::DeckError;
+use crate::cards::{Card, ResourceType};
+use crate::constants::{MAX_COPIES_PER_CARD, MAX_DECK_SIZE, MIN_DECK_SIZE};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path::Path};
+
+/// The rules that a deck has to follow to be allowed in a game
+/// Formats can be loaded from a RON file, see `assets/formats/standard.ron`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeckFormat {
+    /// The minimum amount of cards in a deck
+    pub min_size: usize,
+
+    /// The maximum amount of cards in a deck
+    pub max_size: usize,
+
+    /// The maximum amount of copies of a card with the same name
+    pub max_copies: usize,
+
+    /// The resources that the cards in the deck are allowed to cost
+    pub allowed_resources: Vec<ResourceType>,
+
+    /// The ids of the cards that are not allowed in a deck
+    #[serde(default)]
+    pub banned: Vec<String>,
+}
+
+impl DeckFormat {
+    /// Load a format from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<DeckFormat, DeckError> {
+        let contents = fs::read_to_string(path).map_err(DeckError::Io)?;
+        ron::from_str(&contents).map_err(|e| DeckError::Parse {
+            line: e.position.line,
+            reason: e.code.to_string(),
+        })
+    }
+}
+
+impl Default for DeckFormat {
+    /// The standard format, which allows every card and every resource
+    fn default() -> DeckFormat {
+        DeckFormat {
+            min_size: MIN_DECK_SIZE,
+            max_size: MAX_DECK_SIZE,
+            max_copies: MAX_COPIES_PER_CARD,
+            allowed_resources: ResourceType::ALL.to_vec(),
+            banned: Vec::new(),
+        }
+    }
+}
+
+/// Checks if decks follow the rules of a `DeckFormat`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeckValidator {
+    pub format: DeckFormat,
+}
+
+impl DeckValidator {
+    pub fn new(format: DeckFormat) -> DeckValidator {
+        DeckValidator { format }
+    }
+
+    /// Check the given deck against the format
+    /// Returns every rule that the deck breaks, so the player can fix them all at once
+    pub fn validate(&self, cards: &[Box<dyn Card>]) -> Result<(), Vec<DeckViolation>> {
+        let format = &self.format;
+        let mut violations = Vec::new();
+        if cards.len() < format.min_size {
+            violations.push(DeckViolation::TooFewCards {
+                size: cards.len(),
+                min: format.min_size,
+            });
+        }
+        if cards.len() > format.max_size {
+            violations.push(DeckViolation::TooManyCards {
+                size: cards.len(),
+                max: format.max_size,
+            });
+        }
+
+        // Every card only has to be checked once, in the order that it first shows up in the deck
+        let mut checked: Vec<&str> = Vec::new();
+        for card in cards {
+            if checked.contains(&card.name()) {
+                continue;
+            }
+            checked.push(card.name());
+
+            let copies = cards
+                .iter()
+                .filter(|other| other.name() == card.name())
+                .count();
+            if copies > format.max_copies {
+                violations.push(DeckViolation::TooManyCopies {
+                    name: card.name().to_string(),
+                    copies,
+                    max: format.max_copies,
+                });
+            }
+            for (resource, _) in card.cost() {
+                if !format.allowed_resources.contains(&resource) {
+                    violations.push(DeckViolation::ResourceNotAllowed {
+                        name: card.name().to_string(),
+                        resource,
+                    });
+                }
+            }
+            if format.banned.iter().any(|id| id == card.id()) {
+                violations.push(DeckViolation::Banned {
+                    name: card.name().to_string(),
+                });
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// A rule of a `DeckFormat` that a deck breaks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckViolation {
+    /// The deck has less cards than the format allows
+    TooFewCards { size: usize, min: usize },
+    /// The deck has more cards than the format allows
+    TooManyCards { size: usize, max: usize },
+    /// The deck has more copies of a card than the format allows
+    TooManyCopies {
+        name: String,
+        copies: usize,
+        max: usize,
+    },
+    /// A card costs a resource that is not allowed in the format
+    ResourceNotAllowed {
+        name: String,
+        resource: ResourceType,
+    },
+    /// A card is banned in the format
+    Banned { name: String },
+}
+
+impl fmt::Display for DeckViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeckViolation::TooFewCards { size, min } => write!(
+                f,
+                "The deck has {} cards, but needs at least {} cards",
+                size, min
+            ),
+            DeckViolation::TooManyCards { size, max } => write!(
+                f,
+                "The deck has {} cards, but can have at most {} cards",
+                size, max
+            ),
+            DeckViolation::TooManyCopies { name, copies, max } => write!(
+                f,
+                "The deck has {} copies of {}, but can have at most {}",
+                copies, name, max
+            ),
+            DeckViolation::ResourceNotAllowed { name, resource } => {
+                write!(f, "{} costs {:?}, which is not allowed", name, resource)
+            }
+            DeckViolation::Banned { name } => write!(f, "{} is banned", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeckFormat, DeckValidator, DeckViolation};
+    use crate::cards::{Card, ResourceType};
+    use crate::test_utils::{database, starter_deck};
+
+    fn validator() -> DeckValidator {
+        DeckValidator::new(DeckFormat {
+            min_size: 3,
+            max_size: 5,
+            max_copies: 2,
+            allowed_resources: vec![ResourceType::Red],
+            banned: vec!["damage_spell_card".to_string()],
+        })
+    }
+
+    fn cards(ids: &[&str]) -> Vec<Box<dyn Card>> {
+        let database = database();
+        ids.iter()
+            .map(|id| database.create_card(id).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn deck_that_follows_the_format_is_valid() {
+        let deck = cards(&["generic_minion", "generic_minion", "damage_spell_card"]);
+        let mut validator = validator();
+        validator.format.banned.clear();
+        assert_eq!(validator.validate(&deck), Ok(()));
+    }
+
+    #[test]
+    fn every_broken_rule_is_reported() {
+        let validator = validator();
+        assert_eq!(
+            validator.validate(&cards(&["generic_minion", "generic_minion"])),
+            Err(vec![DeckViolation::TooFewCards { size: 2, min: 3 }])
+        );
+        assert_eq!(
+            validator.validate(&cards(&["generic_minion"; 6])),
+            Err(vec![
+                DeckViolation::TooManyCards { size: 6, max: 5 },
+                DeckViolation::TooManyCopies {
+                    name: "Generic minion".to_string(),
+                    copies: 6,
+                    max: 2,
+                },
+            ])
+        );
+        assert_eq!(
+            validator.validate(&cards(&[
+                "light_elemental",
+                "generic_minion",
+                "damage_spell_card"
+            ])),
+            Err(vec![
+                DeckViolation::ResourceNotAllowed {
+                    name: "Light elemental".to_string(),
+                    resource: ResourceType::White,
+                },
+                DeckViolation::Banned {
+                    name: "Damage spell card".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn starter_deck_follows_the_standard_format() {
+        let deck = starter_deck().create_cards(&database()).unwrap();
+        assert_eq!(DeckValidator::default().validate(&deck), Ok(()));
+    }
+}
@@ -0,0 +1,39 @@
+use super::Target;
+use crate::cards::ResourceType;
+use serde::{Deserialize, Serialize};
+
+/// Everything that the active player can do during their turn
+/// Every move goes through `GameState::apply`, no matter if it comes from the mouse, an AI, the network or a replay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// Add a resource of the given type to the pool at the start of the turn
+    SelectResource(ResourceType),
+    /// Play the minion at index `from` in the hand onto the field, at index `to`
+    PlayCard { from: usize, to: usize },
+    /// Attack the target with the minion at index `attacker` on the field
+    Attack { attacker: usize, target: Target },
+    /// Cast the spell at index `card` in the hand on the target
+    CastSpell { card: usize, target: Target },
+    /// Move the turn to the next phase
+    AdvancePhase,
+    /// End the turn and pass it to the other side
+    EndTurn,
+}
+
+impl Action {
+    /// Get the same action as it's seen from the other side of the board
+    /// Both players see themselves as `Side::Player`, so actions that are sent to the other player have to be mirrored
+    pub fn mirrored(self) -> Action {
+        match self {
+            Action::Attack { attacker, target } => Action::Attack {
+                attacker,
+                target: target.mirrored(),
+            },
+            Action::CastSpell { card, target } => Action::CastSpell {
+                card,
+                target: target.mirrored(),
+            },
+            action => action,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use super::{Event, GameState, Target};
+use crate::cards::Effect;
+use crate::constants::{MAX_FIELD_SIZE, STARTING_HEALTH};
+
+impl GameState {
+    /// Resolve the effects of a played card, in the order that the card returned them
+    /// Dead minions are only removed after all effects are resolved, so minion targets keep pointing at the same minion
+    pub(super) fn resolve_effects(&mut self, effects: Vec<Effect>) {
+        for effect in effects {
+            match effect {
+                Effect::Damage { target, amount } => self.deal_damage(target, amount),
+                Effect::Heal { target, amount } => {
+                    self.heal(target, amount);
+                    self.events.push(Event::Healed { target, amount });
+                }
+                Effect::Buff {
+                    target,
+                    attack,
+                    health,
+                } => {
+                    self.buff(target, attack, health);
+                    self.events.push(Event::Buffed {
+                        target,
+                        attack,
+                        health,
+                    });
+                }
+                Effect::Draw { side, amount } => {
+                    for _ in 0..amount {
+                        self.draw_card(side);
+                    }
+                }
+                Effect::Summon { side, card } => {
+                    let player = self.player_at_mut(side);
+                    if player.field.len() < MAX_FIELD_SIZE {
+                        let instance = player.create_instance(card);
+                        let id = instance.id;
+                        player.field.push(instance);
+                        self.events.push(Event::Summoned { side, card: id });
+                    }
+                }
+            }
+        }
+        self.check_deaths();
+    }
+
+    /// Deal the given amount of damage to a target
+    /// Minions can not drop below 0 health, heroes can
+    pub(super) fn deal_damage(&mut self, target: Target, amount: u8) {
+        self.events.push(Event::Damaged { target, amount });
+        match target {
+            Target::Hero(side) => self.player_at_mut(side).health -= i32::from(amount),
+            Target::Minion(side, index) => {
+                if let Some(health) = self
+                    .player_at_mut(side)
+                    .field
+                    .get_mut(index)
+                    .and_then(|instance| instance.card.health_mut())
+                {
+                    *health = health.saturating_sub(amount);
+                }
+            }
+        }
+    }
+
+    /// Restore health of a target
+    /// Heroes can not be healed above their starting health, and minions not above the health of their card. Something
+    /// that already has more health than that, e.g. a hero in a puzzle or a buffed minion, keeps its health
+    fn heal(&mut self, target: Target, amount: u8) {
+        match target {
+            Target::Hero(side) => {
+                let player = self.player_at_mut(side);
+                let max_health = player.health.max(STARTING_HEALTH);
+                player.health = (player.health + i32::from(amount)).min(max_health);
+            }
+            Target::Minion(side, index) => {
+                if let Some(instance) = self.player_at_mut(side).field.get_mut(index) {
+                    let max_health = instance.card.max_health();
+                    if let Some(health) = instance.card.health_mut() {
+                        let max_health = max_health.unwrap_or(*health).max(*health);
+                        *health = health.saturating_add(amount).min(max_health);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Give a minion extra attack and health. This does nothing when targetting a hero
+    fn buff(&mut self, target: Target, attack: u8, health: u8) {
+        if let Target::Minion(side, index) = target {
+            if let Some(instance) = self.player_at_mut(side).field.get_mut(index) {
+                if let Some(value) = instance.card.attack_mut() {
+                    *value = value.saturating_add(attack);
+                }
+                if let Some(value) = instance.card.health_mut() {
+                    *value = value.saturating_add(health);
+                }
+            }
+        }
+    }
+}
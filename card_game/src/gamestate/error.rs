@@ -0,0 +1,82 @@
+use super::{Phase, Side};
+use crate::cards::ResourceType;
+use crate::deck::DeckViolation;
+use std::fmt;
+
+/// The reasons why the rules of the game can reject something that a player tries to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleError {
+    /// The player tried to do something while it's not their turn
+    NotYourTurn,
+    /// The action is not allowed in the phase that the current turn is in
+    WrongPhase(Phase),
+    /// The player does not have enough resources of the given type available to pay for a card
+    NotEnoughResources {
+        resource: ResourceType,
+        required: u8,
+        available: u8,
+    },
+    /// The field already holds the maximum amount of minions
+    FieldFull,
+    /// The referenced card does not exist
+    NoSuchCard,
+    /// A minion can not be put at that position on the field
+    InvalidPosition,
+    /// The card is not a minion, so it can not be put on the field
+    NotAMinion,
+    /// The card is not a minion that can attack
+    CannotAttack,
+    /// The minion was summoned this turn, and can only attack from the next turn on
+    SummoningSick,
+    /// The minion already attacked this turn
+    AlreadyAttacked,
+    /// The given target can not be targetted by this card
+    InvalidTarget,
+    /// The game is already over
+    GameOver,
+    /// The deck of the given side breaks the rules of the format, so the game can not start
+    InvalidDeck {
+        side: Side,
+        violations: Vec<DeckViolation>,
+    },
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleError::NotYourTurn => write!(f, "It is not your turn"),
+            RuleError::WrongPhase(phase) => {
+                write!(f, "This can not be done in the {:?} phase", phase)
+            }
+            RuleError::NotEnoughResources {
+                resource,
+                required,
+                available,
+            } => write!(
+                f,
+                "Not enough {:?} resources: {} required, but only {} available",
+                resource, required, available
+            ),
+            RuleError::FieldFull => write!(f, "There is no more room on the field"),
+            RuleError::NoSuchCard => write!(f, "That card does not exist"),
+            RuleError::InvalidPosition => write!(f, "A minion can not be put there"),
+            RuleError::NotAMinion => write!(f, "This card is not a minion"),
+            RuleError::CannotAttack => write!(f, "This card can not attack"),
+            RuleError::SummoningSick => {
+                write!(f, "Minions can not attack on the turn they are summoned")
+            }
+            RuleError::AlreadyAttacked => write!(f, "This minion already attacked this turn"),
+            RuleError::InvalidTarget => write!(f, "That is not a valid target"),
+            RuleError::GameOver => write!(f, "The game is over"),
+            RuleError::InvalidDeck { side, violations } => {
+                write!(f, "The deck of {:?} is not valid:", side)?;
+                for violation in violations {
+                    write!(f, "\n- {}", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
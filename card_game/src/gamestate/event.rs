@@ -0,0 +1,120 @@
+use super::{GameResult, Phase, Side, Target};
+use crate::cards::ResourceType;
+use serde::{Deserialize, Serialize};
+
+/// Something that happened in the game as the result of an `Action`
+/// Cards are referenced by the id of their `CardInstance`, because their index changes when cards move around
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Event {
+    /// The side added a resource to their pool
+    ResourceSelected { side: Side, resource: ResourceType },
+    /// A new turn started for the given side
+    TurnStarted { side: Side, number: u32 },
+    /// The current turn moved to another phase
+    PhaseChanged(Phase),
+    /// The side drew a card from their deck
+    CardDrawn { side: Side, card: u32 },
+    /// The side had to draw a card, but their deck was empty
+    DeckedOut { side: Side },
+    /// A minion was played from the hand onto the field, at index `position`
+    MinionPlayed {
+        side: Side,
+        card: u32,
+        position: usize,
+    },
+    /// A spell was cast from the hand on the target
+    SpellCast {
+        side: Side,
+        card: u32,
+        target: Target,
+    },
+    /// A minion attacked the target
+    Attacked {
+        side: Side,
+        card: u32,
+        target: Target,
+    },
+    /// The target took damage
+    Damaged { target: Target, amount: u8 },
+    /// The target got healed
+    Healed { target: Target, amount: u8 },
+    /// The minion got extra attack and health
+    Buffed {
+        target: Target,
+        attack: u8,
+        health: u8,
+    },
+    /// A minion was put on the field by the effect of another card
+    Summoned { side: Side, card: u32 },
+    /// A minion died and was moved to the graveyard
+    MinionDied { side: Side, card: u32 },
+    /// The game is over
+    GameEnded(GameResult),
+}
+
+impl Event {
+    /// Get the same event as it's seen from the other side of the board, see `Action::mirrored`
+    pub fn mirrored(self) -> Event {
+        match self {
+            Event::ResourceSelected { side, resource } => Event::ResourceSelected {
+                side: side.other(),
+                resource,
+            },
+            Event::TurnStarted { side, number } => Event::TurnStarted {
+                side: side.other(),
+                number,
+            },
+            Event::PhaseChanged(phase) => Event::PhaseChanged(phase),
+            Event::CardDrawn { side, card } => Event::CardDrawn {
+                side: side.other(),
+                card,
+            },
+            Event::DeckedOut { side } => Event::DeckedOut { side: side.other() },
+            Event::MinionPlayed {
+                side,
+                card,
+                position,
+            } => Event::MinionPlayed {
+                side: side.other(),
+                card,
+                position,
+            },
+            Event::SpellCast { side, card, target } => Event::SpellCast {
+                side: side.other(),
+                card,
+                target: target.mirrored(),
+            },
+            Event::Attacked { side, card, target } => Event::Attacked {
+                side: side.other(),
+                card,
+                target: target.mirrored(),
+            },
+            Event::Damaged { target, amount } => Event::Damaged {
+                target: target.mirrored(),
+                amount,
+            },
+            Event::Healed { target, amount } => Event::Healed {
+                target: target.mirrored(),
+                amount,
+            },
+            Event::Buffed {
+                target,
+                attack,
+                health,
+            } => Event::Buffed {
+                target: target.mirrored(),
+                attack,
+                health,
+            },
+            Event::Summoned { side, card } => Event::Summoned {
+                side: side.other(),
+                card,
+            },
+            Event::MinionDied { side, card } => Event::MinionDied {
+                side: side.other(),
+                card,
+            },
+            Event::GameEnded(result) => Event::GameEnded(result.mirrored()),
+        }
+    }
+}
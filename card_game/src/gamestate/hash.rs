@@ -0,0 +1,142 @@
+use super::{GameState, Player, Side};
+use crate::cards::ResourceType;
+use crate::CardInstance;
+
+impl GameState {
+    /// Calculate a hash of everything in the game that has an effect on how the game continues
+    /// Two games with the same hash play out the same, even if they got there with different actions
+    /// The ids of card instances are left out, because they don't change how a card plays
+    pub fn state_hash(&self) -> u64 {
+        self.state_hash_for(Side::Player)
+    }
+
+    /// Calculate the hash of the game as it's seen by the given side, see `state_hash`
+    /// The other player in a network game sees the same game from the other side of the board. The hash of their
+    /// `Side::Player` is the same as the hash of our `Side::Opponent`, so the two games can be compared
+    /// The hash is the same on every platform and with every version of Rust, see `StateHasher`
+    pub fn state_hash_for(&self, side: Side) -> u64 {
+        let mut hasher = StateHasher::new();
+        hasher.write_bool(self.turn.active == side);
+        hasher.write_u32(self.turn.number);
+        hasher.write_u8(self.turn.phase as u8);
+        hash_player(self.player_at(side), &mut hasher);
+        hash_player(self.player_at(side.other()), &mut hasher);
+        hasher.hash
+    }
+}
+
+/// The offset basis and the prime of the 64 bit FNV-1a hash
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Calculates a 64 bit FNV-1a hash
+/// The hashers of the standard library can change between versions of Rust, and `usize` values are hashed with the
+/// width of the platform. Network games compare hashes between different builds of the game, so every value is
+/// written with a fixed width here
+struct StateHasher {
+    hash: u64,
+}
+
+impl StateHasher {
+    fn new() -> StateHasher {
+        StateHasher {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= u64::from(*byte);
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Write the length of a list or a string
+    /// Nothing in a game comes close to `u32::MAX` items, so the length always fits
+    fn write_len(&mut self, len: usize) {
+        self.write_u32(len as u32);
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.write(value.as_bytes());
+    }
+
+    fn write_option_u8(&mut self, value: Option<&u8>) {
+        match value {
+            Some(value) => {
+                self.write_bool(true);
+                self.write_u8(*value);
+            }
+            None => self.write_bool(false),
+        }
+    }
+
+    fn write_resources(&mut self, resources: &[(ResourceType, u8)]) {
+        self.write_len(resources.len());
+        for (resource, amount) in resources {
+            self.write_u8(*resource as u8);
+            self.write_u8(*amount);
+        }
+    }
+}
+
+fn hash_player(player: &Player, hasher: &mut StateHasher) {
+    hasher.write_i32(player.health);
+    hasher.write_resources(&player.resources);
+    hasher.write_resources(&player.available_resources);
+    hasher.write_bool(player.decked_out);
+    // The deck is never shuffled, so the cards that are left in the deck only depend on how many cards were drawn
+    hasher.write_len(player.deck.len());
+    hasher.write_len(player.graveyard.len());
+    hasher.write_len(player.hand.len());
+    for instance in &player.hand {
+        hash_card(instance, hasher);
+    }
+    hasher.write_len(player.field.len());
+    for instance in &player.field {
+        hash_card(instance, hasher);
+        hasher.write_bool(instance.summoning_sick);
+        hasher.write_bool(instance.has_attacked);
+    }
+}
+
+fn hash_card(instance: &CardInstance, hasher: &mut StateHasher) {
+    hasher.write_str(instance.card.id());
+    hasher.write_option_u8(instance.card.attack());
+    hasher.write_option_u8(instance.card.health());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StateHasher;
+
+    #[test]
+    fn state_hasher_is_fnv_1a() {
+        // The published test vectors of the 64 bit FNV-1a hash
+        let hash = |bytes: &[u8]| {
+            let mut hasher = StateHasher::new();
+            hasher.write(bytes);
+            hasher.hash
+        };
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -0,0 +1,90 @@
+use super::{Action, GameState, Phase, Target};
+use crate::cards::{CardPlayEffect, ResourceType};
+use crate::constants::MAX_FIELD_SIZE;
+
+impl GameState {
+    /// Get every action that the active player is allowed to take right now
+    /// Minions are only put at the right end of the field, because the position on the field has no effect on the game
+    /// This is empty when the game is over
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        if self.result().is_some() {
+            return actions;
+        }
+        let side = self.turn.active;
+        let player = self.player_at(side);
+        match self.turn.phase {
+            Phase::StartOfTurn => {
+                for resource in &ResourceType::ALL {
+                    actions.push(Action::SelectResource(*resource));
+                }
+                actions.push(Action::AdvancePhase);
+            }
+            Phase::Main | Phase::Combat => {
+                if self.turn.phase == Phase::Main {
+                    for (index, instance) in player.hand.iter().enumerate() {
+                        if player.can_pay(&instance.card.cost()).is_err() {
+                            continue;
+                        }
+                        match instance.card.play_effects().into_iter().next() {
+                            Some(CardPlayEffect::SummonMinion)
+                                if player.field.len() < MAX_FIELD_SIZE =>
+                            {
+                                actions.push(Action::PlayCard {
+                                    from: index,
+                                    to: player.field.len(),
+                                });
+                            }
+                            Some(CardPlayEffect::Target(target_type)) => {
+                                for target in self.all_targets() {
+                                    if self.is_valid_target(side, target_type, target) {
+                                        actions.push(Action::CastSpell {
+                                            card: index,
+                                            target,
+                                        });
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                let opponent = self.player_at(side.other());
+                for (index, instance) in player.field.iter().enumerate() {
+                    if !instance.can_attack() {
+                        continue;
+                    }
+                    actions.push(Action::Attack {
+                        attacker: index,
+                        target: Target::Hero(side.other()),
+                    });
+                    for target in 0..opponent.field.len() {
+                        actions.push(Action::Attack {
+                            attacker: index,
+                            target: Target::Minion(side.other(), target),
+                        });
+                    }
+                }
+                // Advancing from the combat phase ends the turn, so that's only listed once
+                if self.turn.phase == Phase::Main {
+                    actions.push(Action::AdvancePhase);
+                }
+                actions.push(Action::EndTurn);
+            }
+            Phase::Draw | Phase::End => {}
+        }
+        actions
+    }
+
+    /// Get every hero and minion on the board, starting with the active side
+    fn all_targets(&self) -> Vec<Target> {
+        let mut targets = Vec::new();
+        for side in &[self.turn.active, self.turn.active.other()] {
+            targets.push(Target::Hero(*side));
+            for index in 0..self.player_at(*side).field.len() {
+                targets.push(Target::Minion(*side, index));
+            }
+        }
+        targets
+    }
+}
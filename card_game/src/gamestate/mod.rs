@@ -0,0 +1,441 @@
+mod action;
+mod effects;
+mod error;
+mod event;
+mod hash;
+mod legal;
+mod player;
+mod result;
+mod target;
+mod turn;
+mod view;
+//mod iter;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::action::Action;
+pub use self::error::RuleError;
+pub use self::event::Event;
+pub use self::player::Player;
+pub use self::result::GameResult;
+pub use self::target::Target;
+pub use self::turn::{Phase, Side, TurnState};
+pub use self::view::{PlayerView, SideView};
+
+use crate::cards::{CardPlayEffect, ResourceType, TargetType};
+use crate::constants::{MAX_FIELD_SIZE, STARTING_HAND_SIZE};
+use crate::deck::DeckValidator;
+use crate::utils::VecUtils;
+
+/// The state of the current game
+/// This holds both of the players data, and enforces the rules on everything that the players do
+#[derive(Clone)]
+pub struct GameState {
+    // The current player
+    pub player: Player,
+
+    // The players opponent
+    pub opponent: Player,
+
+    // Whose turn it is and what phase that turn is in
+    pub turn: TurnState,
+
+    // The rules that both decks have to follow before the game can start
+    pub deck_validator: DeckValidator,
+
+    // The side that takes the first turn when the game starts
+    pub starting_side: Side,
+
+    // The events that happened while applying the current action
+    events: Vec<Event>,
+
+    // Every action that was applied since the start of the game, in order
+    history: Vec<Action>,
+}
+
+impl GameState {
+    pub fn new(player: Player, opponent: Player) -> GameState {
+        GameState {
+            player,
+            opponent,
+            turn: TurnState::default(),
+            deck_validator: DeckValidator::default(),
+            starting_side: Side::Player,
+            events: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Get the player that is sitting at the given side of the board
+    pub fn player_at(&self, side: Side) -> &Player {
+        match side {
+            Side::Player => &self.player,
+            Side::Opponent => &self.opponent,
+        }
+    }
+
+    /// Get a mutable reference to the player that is sitting at the given side of the board
+    pub fn player_at_mut(&mut self, side: Side) -> &mut Player {
+        match side {
+            Side::Player => &mut self.player,
+            Side::Opponent => &mut self.opponent,
+        }
+    }
+
+    /// Get the player whose turn it currently is
+    pub fn active_player(&self) -> &Player {
+        self.player_at(self.turn.active)
+    }
+
+    /// Get the result of the game, or None if the game is still going on
+    /// A side loses when their hero has no health left or when they had to draw from an empty deck
+    /// If both sides lose at the same time, the game is a draw
+    pub fn result(&self) -> Option<GameResult> {
+        match (self.player.is_defeated(), self.opponent.is_defeated()) {
+            (true, true) => Some(GameResult::Draw),
+            (true, false) => Some(GameResult::Winner(Side::Opponent)),
+            (false, true) => Some(GameResult::Winner(Side::Player)),
+            (false, false) => None,
+        }
+    }
+
+    /// Apply an action of the player at the given side to the game
+    /// This is the only way that players can change the game. The action is checked against the rules first, and
+    /// if it's not allowed, an error is returned and nothing changes
+    /// Only the active side can take actions, everyone else gets `RuleError::NotYourTurn`
+    /// Returns everything that happened as a result of the action, in order
+    pub fn apply(&mut self, side: Side, action: Action) -> Result<Vec<Event>, RuleError> {
+        self.check_game_running()?;
+        if side != self.turn.active {
+            return Err(RuleError::NotYourTurn);
+        }
+        self.events.clear();
+        match action {
+            Action::SelectResource(resource) => self.select_resource(resource)?,
+            Action::PlayCard { from, to } => self.play_card(from, to)?,
+            Action::Attack { attacker, target } => self.attack(attacker, target)?,
+            Action::CastSpell { card, target } => self.cast_spell(card, target)?,
+            Action::AdvancePhase => self.advance_phase(),
+            Action::EndTurn => {
+                // The card of the turn is drawn at the start of the turn, so that part of the turn can't be skipped
+                if self.turn.phase == Phase::StartOfTurn {
+                    return Err(RuleError::WrongPhase(self.turn.phase));
+                }
+                self.end_turn()
+            }
+        }
+        self.history.push(action);
+        if let Some(result) = self.result() {
+            self.events.push(Event::GameEnded(result));
+        }
+        Ok(std::mem::take(&mut self.events))
+    }
+
+    /// Get every action that was applied since the start of the game, in order
+    /// The game has no random effects: decks are never shuffled, and no card does anything random. Applying these
+    /// actions to a new game with the same decks results in exactly the same game. Replays and network games rely on
+    /// this, so they only need the decks and the actions
+    pub fn history(&self) -> &[Action] {
+        &self.history
+    }
+
+    /// Replace the history of the game, e.g. when a saved game is loaded
+    /// The actions are not applied, they have to be the actions that led to the game as it is right now
+    pub fn restore_history(&mut self, history: Vec<Action>) {
+        self.history = history;
+    }
+
+    /// Returns an error if the game is over and no more actions can be taken
+    fn check_game_running(&self) -> Result<(), RuleError> {
+        match self.result() {
+            Some(_) => Err(RuleError::GameOver),
+            None => Ok(()),
+        }
+    }
+
+    /// Start a new game
+    /// This checks both decks against the `deck_validator`, resets both decks, draws the starting hands and starts the
+    /// first turn for the `starting_side`
+    /// If one of the decks is not valid, the game does not start and nothing changes
+    pub fn start_game(&mut self) -> Result<(), RuleError> {
+        for side in &[Side::Player, Side::Opponent] {
+            self.deck_validator
+                .validate(&self.player_at(*side).original_deck)
+                .map_err(|violations| RuleError::InvalidDeck {
+                    side: *side,
+                    violations,
+                })?;
+        }
+        self.turn = TurnState::new(self.starting_side);
+        self.events.clear();
+        self.history.clear();
+        for side in &[Side::Player, Side::Opponent] {
+            let player = self.player_at_mut(*side);
+            player.reset_deck();
+            for _ in 0..STARTING_HAND_SIZE {
+                player.draw_card();
+            }
+        }
+        self.start_turn();
+        Ok(())
+    }
+
+    /// Start the turn of the active player
+    /// The turn stays in the start-of-turn phase until the player has selected a resource with `select_resource`
+    fn start_turn(&mut self) {
+        self.events.push(Event::TurnStarted {
+            side: self.turn.active,
+            number: self.turn.number,
+        });
+        self.set_phase(Phase::StartOfTurn);
+
+        // All minions of the active player are ready to attack again
+        for instance in &mut self.player_at_mut(self.turn.active).field {
+            instance.summoning_sick = false;
+            instance.has_attacked = false;
+        }
+    }
+
+    /// Add a resource of the given type to the active player's pool
+    /// This can only be done once per turn, at the start of the turn. After this the player's resources get
+    /// refreshed, they draw a card and the turn moves on to the main phase
+    fn select_resource(&mut self, resource: ResourceType) -> Result<(), RuleError> {
+        if self.turn.phase != Phase::StartOfTurn {
+            return Err(RuleError::WrongPhase(self.turn.phase));
+        }
+        let side = self.turn.active;
+        self.player_at_mut(side).add_resource(resource);
+        self.events.push(Event::ResourceSelected { side, resource });
+        self.finish_start_of_turn();
+        Ok(())
+    }
+
+    /// Run the rest of the start-of-turn phase and the draw phase for the active player, leaving the turn in the main phase
+    fn finish_start_of_turn(&mut self) {
+        self.player_at_mut(self.turn.active).refresh_resources();
+
+        self.set_phase(Phase::Draw);
+        self.draw_card(self.turn.active);
+
+        self.set_phase(Phase::Main);
+    }
+
+    /// Move the current turn to the given phase
+    /// Nothing happens if the turn is already in that phase
+    fn set_phase(&mut self, phase: Phase) {
+        if self.turn.phase != phase {
+            self.turn.phase = phase;
+            self.events.push(Event::PhaseChanged(phase));
+        }
+    }
+
+    /// Let the given side draw a card from their deck
+    fn draw_card(&mut self, side: Side) {
+        let player = self.player_at_mut(side);
+        player.draw_card();
+        let event = match player.hand.last() {
+            Some(instance) if !player.decked_out => Event::CardDrawn {
+                side,
+                card: instance.id,
+            },
+            _ => Event::DeckedOut { side },
+        };
+        self.events.push(event);
+    }
+
+    /// Move the current turn to the next phase
+    /// Advancing from the start of the turn skips adding a resource to the pool
+    /// The main phase moves to combat, and combat ends the turn
+    fn advance_phase(&mut self) {
+        match self.turn.phase {
+            Phase::StartOfTurn => self.finish_start_of_turn(),
+            Phase::Main => self.set_phase(Phase::Combat),
+            _ => self.end_turn(),
+        }
+    }
+
+    /// End the current turn and pass the turn to the other side
+    /// The next player will then have to select a resource to start their turn
+    fn end_turn(&mut self) {
+        self.set_phase(Phase::End);
+
+        self.turn.active = self.turn.active.other();
+        self.turn.number += 1;
+        self.start_turn();
+    }
+
+    /// Play the minion at index `card` in the active player's hand onto their field, at index `position`
+    /// The cost of the card is paid before it leaves the hand. If the player can not pay for the card, it stays in the hand
+    fn play_card(&mut self, card: usize, position: usize) -> Result<(), RuleError> {
+        self.can_play_card_from_hand()?;
+        let side = self.turn.active;
+        let player = self.player_at(side);
+        if position > player.field.len() {
+            return Err(RuleError::InvalidPosition);
+        }
+        let cost = {
+            let instance = player.hand.get(card).ok_or(RuleError::NoSuchCard)?;
+            let is_minion = instance
+                .card
+                .play_effects()
+                .iter()
+                .any(|effect| matches!(effect, CardPlayEffect::SummonMinion));
+            if !is_minion {
+                return Err(RuleError::NotAMinion);
+            }
+            instance.card.cost()
+        };
+        self.player_at_mut(side).pay(&cost)?;
+
+        let mut instance = self.player_at_mut(side).hand.remove(card);
+        instance.summoning_sick = true;
+        let effects = instance.card.on_play(side, None, self);
+        self.events.push(Event::MinionPlayed {
+            side,
+            card: instance.id,
+            position,
+        });
+        let inserted = self
+            .player_at_mut(side)
+            .field
+            .push_or_insert(position, instance);
+        debug_assert!(
+            inserted,
+            "The position is checked before the card is paid for"
+        );
+        self.resolve_effects(effects);
+        Ok(())
+    }
+
+    /// Attack the given target with the minion at index `attacker` on the active player's field
+    /// When attacking a minion, both minions deal their attack as damage to each other at the same time
+    /// Every minion can only attack once per turn, and not on the turn that it was summoned
+    /// The first attack of a turn moves the turn to the combat phase
+    fn attack(&mut self, attacker: usize, target: Target) -> Result<(), RuleError> {
+        if self.turn.phase != Phase::Main && self.turn.phase != Phase::Combat {
+            return Err(RuleError::WrongPhase(self.turn.phase));
+        }
+        let side = self.turn.active;
+        let damage = {
+            let instance = self
+                .player_at(side)
+                .field
+                .get(attacker)
+                .ok_or(RuleError::NoSuchCard)?;
+            if instance.summoning_sick {
+                return Err(RuleError::SummoningSick);
+            }
+            if instance.has_attacked {
+                return Err(RuleError::AlreadyAttacked);
+            }
+            *instance.card.attack().ok_or(RuleError::CannotAttack)?
+        };
+        if target.side() != side.other() {
+            return Err(RuleError::InvalidTarget);
+        }
+        // Heroes don't hit back, minions hit back with their own attack
+        let retaliation = match target {
+            Target::Hero(_) => None,
+            Target::Minion(target_side, index) => Some(
+                self.player_at(target_side)
+                    .field
+                    .get(index)
+                    .ok_or(RuleError::NoSuchCard)?
+                    .card
+                    .attack()
+                    .cloned()
+                    .unwrap_or(0),
+            ),
+        };
+
+        self.set_phase(Phase::Combat);
+        self.events.push(Event::Attacked {
+            side,
+            card: self.player_at(side).field[attacker].id,
+            target,
+        });
+        self.deal_damage(target, damage);
+        if let Some(retaliation) = retaliation {
+            self.deal_damage(Target::Minion(side, attacker), retaliation);
+        }
+        self.player_at_mut(side).field[attacker].has_attacked = true;
+        self.check_deaths();
+        Ok(())
+    }
+
+    /// Cast the spell at index `card` in the active player's hand on the given target
+    /// The target has to match the `TargetType` of the spell, and the active player has to be able to pay for it
+    /// After the spell resolves, it gets moved to the graveyard
+    fn cast_spell(&mut self, card: usize, target: Target) -> Result<(), RuleError> {
+        if self.turn.phase != Phase::Main {
+            return Err(RuleError::WrongPhase(self.turn.phase));
+        }
+        let side = self.turn.active;
+        let (target_type, cost) = {
+            let instance = self
+                .player_at(side)
+                .hand
+                .get(card)
+                .ok_or(RuleError::NoSuchCard)?;
+            let target_type = instance
+                .card
+                .play_effects()
+                .into_iter()
+                .find_map(|effect| match effect {
+                    CardPlayEffect::Target(target_type) => Some(target_type),
+                    _ => None,
+                })
+                .ok_or(RuleError::InvalidTarget)?;
+            (target_type, instance.card.cost())
+        };
+        if !self.is_valid_target(side, target_type, target) {
+            return Err(RuleError::InvalidTarget);
+        }
+        self.player_at_mut(side).pay(&cost)?;
+
+        let instance = self.player_at_mut(side).hand.remove(card);
+        let effects = instance.card.on_play(side, Some(target), self);
+        self.events.push(Event::SpellCast {
+            side,
+            card: instance.id,
+            target,
+        });
+        self.player_at_mut(side).graveyard.push(instance);
+        self.resolve_effects(effects);
+        Ok(())
+    }
+
+    /// Returns true if `target` exists and is allowed by `target_type`, for a card played by `caster`
+    pub fn is_valid_target(&self, caster: Side, target_type: TargetType, target: Target) -> bool {
+        let exists = match target {
+            Target::Hero(_) => true,
+            Target::Minion(side, index) => index < self.player_at(side).field.len(),
+        };
+        exists && target_type.contains(target.target_type(caster))
+    }
+
+    /// Move every minion without health from the field to their owner's graveyard
+    /// This should be called after every action that changes the board
+    /// The active player's minions are always processed first, so the result is the same every time
+    fn check_deaths(&mut self) {
+        let active = self.turn.active;
+        for side in &[active, active.other()] {
+            for card in self.player_at_mut(*side).remove_dead_minions() {
+                self.events.push(Event::MinionDied { side: *side, card });
+            }
+        }
+    }
+
+    /// Determines if the active player can play a card from hand
+    /// This checks if it's the main phase and if the active player has room for another minion
+    fn can_play_card_from_hand(&self) -> Result<(), RuleError> {
+        if self.turn.phase != Phase::Main {
+            Err(RuleError::WrongPhase(self.turn.phase))
+        } else if self.active_player().field.len() >= MAX_FIELD_SIZE {
+            Err(RuleError::FieldFull)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use super::RuleError;
+use crate::card_instance::CardInstance;
+use crate::cards::{Card, ResourceType};
+use crate::constants::STARTING_HEALTH;
+use crate::utils::VecUtils;
+
+/// Holds information about a player
+#[derive(Clone)]
+pub struct Player {
+    pub name: String,
+    pub health: i32,
+
+    /// The total pool of resources that this player has built up over their turns
+    pub resources: Vec<(ResourceType, u8)>,
+
+    /// The resources that are still available to spend this turn
+    /// This gets refilled from `resources` at the start of every turn
+    pub available_resources: Vec<(ResourceType, u8)>,
+
+    /// Hold the cards that were in the deck when the game started
+    /// This should not change
+    pub original_deck: Vec<Box<dyn Card>>,
+
+    /// Holds the cards that are not drawn from the deck
+    pub deck: Vec<Box<dyn Card>>,
+
+    /// Holds the cards that are currently in the players hand
+    pub hand: Vec<CardInstance>,
+
+    /// Holds the cards that are currently on this players side of the board
+    pub field: Vec<CardInstance>,
+
+    /// Holds the cards that are currently in the graveyard
+    pub graveyard: Vec<CardInstance>,
+
+    /// Is set when the player had to draw a card from an empty deck, which makes them lose the game
+    pub decked_out: bool,
+
+    /// The id that the next card instance of this player will get
+    next_card_id: u32,
+}
+
+impl Player {
+    /// Create a new player with the given name, 100 health and an empty deck
+    pub fn new<T: ToString>(name: T) -> Player {
+        Player {
+            name: name.to_string(),
+            health: STARTING_HEALTH,
+            original_deck: Vec::new(),
+            resources: Vec::new(),
+            available_resources: Vec::new(),
+            deck: Vec::new(),
+            hand: Vec::new(),
+            field: Vec::new(),
+            graveyard: Vec::new(),
+            decked_out: false,
+            next_card_id: 0,
+        }
+    }
+
+    /// Clear the board state and reset the deck
+    /// This also restores the player's health, so it can be used to start a new game
+    pub fn reset_deck(&mut self) {
+        self.health = STARTING_HEALTH;
+        self.decked_out = false;
+        self.next_card_id = 0;
+        self.deck.clear();
+        self.hand.clear();
+        self.field.clear();
+        self.graveyard.clear();
+        self.resources.clear();
+        self.available_resources.clear();
+
+        for card in &self.original_deck {
+            self.deck.push(card.clone_box());
+        }
+    }
+
+    /// Draw a card from the deck and put it in the players hand
+    /// If the deck is empty, the player is decked out and will lose the game
+    pub fn draw_card(&mut self) {
+        match self.deck.try_take(0) {
+            Some(card) => {
+                let instance = self.create_instance(card);
+                self.hand.push(instance);
+            }
+            None => self.decked_out = true,
+        }
+    }
+
+    /// Create a new instance of a card that is owned by this player, with an id that is unique for this player
+    pub fn create_instance(&mut self, card: Box<dyn Card>) -> CardInstance {
+        let instance = CardInstance::new(self.next_card_id, card);
+        self.next_card_id += 1;
+        instance
+    }
+
+    /// Get the id that the next card instance of this player will get, see `create_instance`
+    pub fn next_card_id(&self) -> u32 {
+        self.next_card_id
+    }
+
+    /// Continue giving out card instance ids from the given id, e.g. when a saved game is loaded
+    pub fn set_next_card_id(&mut self, id: u32) {
+        self.next_card_id = id;
+    }
+
+    /// Returns true if this player's hero is defeated, either by running out of health or out of cards
+    pub fn is_defeated(&self) -> bool {
+        self.health <= 0 || self.decked_out
+    }
+
+    /// Move all minions that have no health left from the field to the graveyard
+    /// The minions end up in the graveyard in the same order as they were on the field
+    /// Returns the ids of the minions that died
+    pub fn remove_dead_minions(&mut self) -> Vec<u32> {
+        let (dead, alive): (Vec<CardInstance>, Vec<CardInstance>) = self
+            .field
+            .drain(..)
+            .partition(|instance| instance.card.health().is_none_or(|health| *health == 0));
+        self.field = alive;
+        let ids = dead.iter().map(|instance| instance.id).collect();
+        self.graveyard.extend(dead);
+        ids
+    }
+
+    /// Recharge all the resources that were spent last turn
+    pub fn refresh_resources(&mut self) {
+        self.available_resources = self.resources.clone();
+    }
+
+    /// Add a single resource of the given type to the players pool
+    /// The resource will be available after the next `refresh_resources`
+    pub fn add_resource(&mut self, resource: ResourceType) {
+        match self.resources.iter_mut().find(|(r, _)| *r == resource) {
+            Some((_, amount)) => *amount += 1,
+            None => self.resources.push((resource, 1)),
+        }
+    }
+
+    /// Get the amount of resources of the given type that the player can still spend this turn
+    pub fn available_resource(&self, resource: ResourceType) -> u8 {
+        Player::amount_of(&self.available_resources, resource)
+    }
+
+    /// Get the total amount of the given resource type in a list of resources
+    /// The same resource type is allowed to show up multiple times in the list
+    fn amount_of(list: &[(ResourceType, u8)], resource: ResourceType) -> u8 {
+        list.iter()
+            .filter(|(r, _)| *r == resource)
+            .map(|(_, amount)| *amount)
+            .sum()
+    }
+
+    /// Checks if the player has enough available resources to pay the given cost
+    /// If not, the first resource type that the player is short on is returned in the error
+    pub fn can_pay(&self, cost: &[(ResourceType, u8)]) -> Result<(), RuleError> {
+        for (resource, _) in cost {
+            let required = Player::amount_of(cost, *resource);
+            let available = self.available_resource(*resource);
+            if available < required {
+                return Err(RuleError::NotEnoughResources {
+                    resource: *resource,
+                    required,
+                    available,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Spend the resources of the given cost. They will come back at the next `refresh_resources`
+    /// Nothing gets spent if the player can not pay the full cost
+    pub fn pay(&mut self, cost: &[(ResourceType, u8)]) -> Result<(), RuleError> {
+        self.can_pay(cost)?;
+        for (resource, amount) in cost {
+            // can_pay guarantees that the available resources hold enough of this type, but they
+            // might be spread out over multiple entries
+            let mut remaining = *amount;
+            for (r, available) in self.available_resources.iter_mut() {
+                if *r == *resource {
+                    let spent = remaining.min(*available);
+                    *available -= spent;
+                    remaining -= spent;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // /// Draw a card and immediately play it on the field
+    // pub fn draw_and_play_card(&mut self) {
+    //     let index = 0;
+    //     let card = self.deck.remove(index);
+    //     self.field.push(self.create_instance(card));
+    // }
+}
@@ -0,0 +1,21 @@
+use super::Side;
+use serde::{Deserialize, Serialize};
+
+/// The way that a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameResult {
+    /// The given side won the game
+    Winner(Side),
+    /// Both heroes were defeated at the same time
+    Draw,
+}
+
+impl GameResult {
+    /// Get the same result as it's seen from the other side of the board
+    pub fn mirrored(self) -> GameResult {
+        match self {
+            GameResult::Winner(side) => GameResult::Winner(side.other()),
+            GameResult::Draw => GameResult::Draw,
+        }
+    }
+}
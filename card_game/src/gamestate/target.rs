@@ -0,0 +1,39 @@
+use super::Side;
+use crate::cards::TargetType;
+use serde::{Deserialize, Serialize};
+
+/// Holds a reference to something on the board that can be attacked, or targetted by a spell
+/// This is either the hero of one of the sides, or a minion on the field of one of the sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Target {
+    Hero(Side),
+    Minion(Side, usize),
+}
+
+impl Target {
+    /// Get the side of the board that this target is on
+    pub fn side(self) -> Side {
+        match self {
+            Target::Hero(side) => side,
+            Target::Minion(side, _) => side,
+        }
+    }
+
+    /// Get the same target as it's seen from the other side of the board
+    pub fn mirrored(self) -> Target {
+        match self {
+            Target::Hero(side) => Target::Hero(side.other()),
+            Target::Minion(side, index) => Target::Minion(side.other(), index),
+        }
+    }
+
+    /// Get the TargetType flag that this target matches, when it's targetted by a card played by `caster`
+    pub fn target_type(self, caster: Side) -> TargetType {
+        match self {
+            Target::Hero(side) if side == caster => TargetType::TARGET_SELF,
+            Target::Hero(_) => TargetType::TARGET_OPPONENT,
+            Target::Minion(side, _) if side == caster => TargetType::TARGET_OWNMINION,
+            Target::Minion(_, _) => TargetType::TARGET_OPPONENTMINION,
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use super::{Action, Event, GameResult, Phase, RuleError, Side, Target};
+use crate::cards::{Card, Effect, HiddenCard, ResourceType};
+use crate::test_utils::{new_game, put_minion, skip_to_main};
+
+#[test]
+fn selecting_a_resource_draws_a_card_and_starts_the_main_phase() {
+    let mut game_state = new_game();
+    assert_eq!(game_state.turn.active, Side::Player);
+    assert_eq!(game_state.turn.phase, Phase::StartOfTurn);
+    let (hand_size, deck_size) = (game_state.player.hand.len(), game_state.player.deck.len());
+
+    game_state
+        .apply(Side::Player, Action::SelectResource(ResourceType::Red))
+        .unwrap();
+    assert_eq!(game_state.turn.phase, Phase::Main);
+    assert_eq!(game_state.player.hand.len(), hand_size + 1);
+    assert_eq!(game_state.player.deck.len(), deck_size - 1);
+    assert_eq!(game_state.player.available_resource(ResourceType::Red), 1);
+
+    // Only one resource can be added per turn
+    assert_eq!(
+        game_state.apply(Side::Player, Action::SelectResource(ResourceType::Red)),
+        Err(RuleError::WrongPhase(Phase::Main))
+    );
+}
+
+#[test]
+fn advancing_from_combat_passes_the_turn() {
+    let mut game_state = new_game();
+    skip_to_main(&mut game_state);
+    game_state
+        .apply(Side::Player, Action::AdvancePhase)
+        .unwrap();
+    assert_eq!(game_state.turn.phase, Phase::Combat);
+    assert_eq!(
+        game_state.apply(Side::Player, Action::PlayCard { from: 0, to: 0 }),
+        Err(RuleError::WrongPhase(Phase::Combat))
+    );
+
+    game_state
+        .apply(Side::Player, Action::AdvancePhase)
+        .unwrap();
+    assert_eq!(game_state.turn.active, Side::Opponent);
+    assert_eq!(game_state.turn.number, 2);
+    assert_eq!(game_state.turn.phase, Phase::StartOfTurn);
+}
+
+#[test]
+fn only_the_active_side_can_take_actions() {
+    let mut game_state = new_game();
+    assert_eq!(
+        game_state.apply(Side::Opponent, Action::AdvancePhase),
+        Err(RuleError::NotYourTurn)
+    );
+    assert_eq!(game_state.turn.phase, Phase::StartOfTurn);
+    game_state
+        .apply(Side::Player, Action::AdvancePhase)
+        .unwrap();
+}
+
+#[test]
+fn end_turn_is_not_allowed_at_the_start_of_the_turn() {
+    let mut game_state = new_game();
+    assert_eq!(
+        game_state.apply(Side::Player, Action::EndTurn),
+        Err(RuleError::WrongPhase(Phase::StartOfTurn))
+    );
+    assert_eq!(game_state.turn.active, Side::Player);
+    assert!(game_state.history().is_empty());
+}
+
+#[test]
+fn cards_can_only_be_played_when_they_are_paid_for() {
+    let mut game_state = new_game();
+    skip_to_main(&mut game_state);
+    // The generic minion is the third card of the starter deck, and costs 3 red resources
+    let play_minion = Action::PlayCard { from: 2, to: 0 };
+    assert_eq!(
+        game_state.apply(Side::Player, play_minion),
+        Err(RuleError::NotEnoughResources {
+            resource: ResourceType::Red,
+            required: 3,
+            available: 0,
+        })
+    );
+    assert_eq!(game_state.player.hand.len(), 6);
+    assert!(game_state.player.field.is_empty());
+
+    game_state.player.available_resources = vec![(ResourceType::Red, 4)];
+    game_state.apply(Side::Player, play_minion).unwrap();
+    assert_eq!(game_state.player.hand.len(), 5);
+    assert_eq!(game_state.player.field[0].card.id(), "generic_minion");
+    assert_eq!(game_state.player.available_resource(ResourceType::Red), 1);
+}
+
+#[test]
+fn minions_attack_once_per_turn_and_not_on_the_turn_they_are_summoned() {
+    let mut game_state = new_game();
+    skip_to_main(&mut game_state);
+    game_state.player.available_resources = vec![(ResourceType::Red, 3)];
+    game_state
+        .apply(Side::Player, Action::PlayCard { from: 2, to: 0 })
+        .unwrap();
+    let attack = Action::Attack {
+        attacker: 0,
+        target: Target::Hero(Side::Opponent),
+    };
+    assert_eq!(
+        game_state.apply(Side::Player, attack),
+        Err(RuleError::SummoningSick)
+    );
+
+    // Pass the opponent's turn, after which the minion is ready
+    game_state.apply(Side::Player, Action::EndTurn).unwrap();
+    skip_to_main(&mut game_state);
+    game_state.apply(Side::Opponent, Action::EndTurn).unwrap();
+    skip_to_main(&mut game_state);
+
+    game_state.apply(Side::Player, attack).unwrap();
+    assert_eq!(game_state.opponent.health, 95);
+    assert_eq!(
+        game_state.apply(Side::Player, attack),
+        Err(RuleError::AlreadyAttacked)
+    );
+}
+
+#[test]
+fn minions_that_kill_each_other_both_go_to_the_graveyard() {
+    let mut game_state = new_game();
+    skip_to_main(&mut game_state);
+    let attacker = put_minion(&mut game_state, Side::Player, "generic_minion");
+    let defender = put_minion(&mut game_state, Side::Opponent, "generic_minion");
+    let attacker_id = game_state.player.field[attacker].id;
+    let defender_id = game_state.opponent.field[defender].id;
+
+    let events = game_state
+        .apply(
+            Side::Player,
+            Action::Attack {
+                attacker,
+                target: Target::Minion(Side::Opponent, defender),
+            },
+        )
+        .unwrap();
+    assert!(game_state.player.field.is_empty());
+    assert!(game_state.opponent.field.is_empty());
+    assert_eq!(game_state.player.graveyard[0].id, attacker_id);
+    assert_eq!(game_state.opponent.graveyard[0].id, defender_id);
+    // The minions of the active side die first
+    let deaths: Vec<_> = events
+        .into_iter()
+        .filter(|event| matches!(event, Event::MinionDied { .. }))
+        .collect();
+    assert_eq!(
+        deaths,
+        vec![
+            Event::MinionDied {
+                side: Side::Player,
+                card: attacker_id,
+            },
+            Event::MinionDied {
+                side: Side::Opponent,
+                card: defender_id,
+            },
+        ]
+    );
+}
+
+#[test]
+fn hero_does_not_hit_back() {
+    let mut game_state = new_game();
+    skip_to_main(&mut game_state);
+    let attacker = put_minion(&mut game_state, Side::Player, "generic_minion");
+    let events = game_state
+        .apply(
+            Side::Player,
+            Action::Attack {
+                attacker,
+                target: Target::Hero(Side::Opponent),
+            },
+        )
+        .unwrap();
+    let damaged: Vec<_> = events
+        .iter()
+        .filter(|event| matches!(event, Event::Damaged { .. }))
+        .collect();
+    assert_eq!(
+        damaged,
+        vec![&Event::Damaged {
+            target: Target::Hero(Side::Opponent),
+            amount: 5,
+        }]
+    );
+    assert_eq!(game_state.opponent.health, 95);
+    assert_eq!(game_state.player.field[attacker].card.health(), Some(&5));
+}
+
+#[test]
+fn player_that_keeps_ending_the_turn_decks_out() {
+    let mut game_state = new_game();
+    let deck_size = game_state.player.deck.len();
+    while game_state.result().is_none() {
+        // Ending the turn right away is rejected, so the card of the turn is always drawn
+        let side = game_state.turn.active;
+        assert!(game_state.apply(side, Action::EndTurn).is_err());
+        game_state.apply(side, Action::AdvancePhase).unwrap();
+        if game_state.result().is_none() {
+            game_state.apply(side, Action::EndTurn).unwrap();
+        }
+        assert!(game_state.turn.number <= 2 * deck_size as u32 + 2);
+    }
+    // The player takes the first turn, so they run out of cards first
+    assert!(game_state.player.decked_out);
+    assert_eq!(
+        game_state.result(),
+        Some(GameResult::Winner(Side::Opponent))
+    );
+    assert_eq!(
+        game_state.apply(Side::Opponent, Action::AdvancePhase),
+        Err(RuleError::GameOver)
+    );
+}
+
+#[test]
+fn game_ends_when_a_hero_dies_and_is_a_draw_when_both_die() {
+    let mut game_state = new_game();
+    skip_to_main(&mut game_state);
+    game_state.player.health = 3;
+    game_state.opponent.health = 3;
+    game_state.player.available_resources = vec![(ResourceType::Red, 2)];
+    // The damage spell is the fourth card of the starter deck. It hits the player, who then has 0 health left
+    let events = game_state
+        .apply(
+            Side::Player,
+            Action::CastSpell {
+                card: 3,
+                target: Target::Hero(Side::Player),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        game_state.result(),
+        Some(GameResult::Winner(Side::Opponent))
+    );
+    assert_eq!(
+        events.last(),
+        Some(&Event::GameEnded(GameResult::Winner(Side::Opponent)))
+    );
+
+    game_state.opponent.health = 0;
+    assert_eq!(game_state.result(), Some(GameResult::Draw));
+}
+
+#[test]
+fn heals_stop_at_the_max_health_and_never_lower_health() {
+    let mut game_state = new_game();
+    let minion = put_minion(&mut game_state, Side::Player, "generic_minion");
+    let heal = |target| Effect::Heal { target, amount: 10 };
+
+    game_state.player.health = 95;
+    game_state.opponent.health = 120;
+    *game_state.player.field[minion].card.health_mut().unwrap() = 2;
+    game_state.resolve_effects(vec![
+        heal(Target::Hero(Side::Player)),
+        heal(Target::Hero(Side::Opponent)),
+        heal(Target::Minion(Side::Player, minion)),
+    ]);
+    assert_eq!(game_state.player.health, 100);
+    assert_eq!(game_state.opponent.health, 120);
+    assert_eq!(game_state.player.field[minion].card.health(), Some(&5));
+
+    // A buffed minion keeps its extra health
+    *game_state.player.field[minion].card.health_mut().unwrap() = 8;
+    game_state.resolve_effects(vec![heal(Target::Minion(Side::Player, minion))]);
+    assert_eq!(game_state.player.field[minion].card.health(), Some(&8));
+}
+
+#[test]
+fn players_only_see_their_own_hand_and_deck() {
+    let game_state = new_game();
+    let view = game_state.view(Side::Player);
+    assert!(view.player(Side::Player).hand().is_some());
+    assert!(view.player(Side::Player).deck().is_some());
+    assert!(view.player(Side::Opponent).hand().is_none());
+    assert!(view.player(Side::Opponent).deck().is_none());
+    assert_eq!(
+        view.player(Side::Opponent).hand_size(),
+        game_state.opponent.hand.len()
+    );
+
+    let known = view.to_game_state();
+    let is_hidden = |card: &dyn Card| card.id() == HiddenCard::ID;
+    assert!(known
+        .player
+        .hand
+        .iter()
+        .all(|instance| !is_hidden(instance.card.as_ref())));
+    assert!(known
+        .opponent
+        .hand
+        .iter()
+        .all(|instance| is_hidden(instance.card.as_ref())));
+    assert!(known
+        .opponent
+        .deck
+        .iter()
+        .chain(&known.opponent.original_deck)
+        .all(|card| is_hidden(card.as_ref())));
+    assert_eq!(known.opponent.deck.len(), game_state.opponent.deck.len());
+}
+
+#[test]
+fn spectators_do_not_see_either_hand_or_deck() {
+    let game_state = new_game();
+    let view = game_state.spectator_view();
+    for side in &[Side::Player, Side::Opponent] {
+        assert!(view.player(*side).hand().is_none());
+        assert!(view.player(*side).deck().is_none());
+    }
+    let known = view.to_game_state();
+    for side in &[Side::Player, Side::Opponent] {
+        let player = known.player_at(*side);
+        assert!(player
+            .hand
+            .iter()
+            .all(|instance| instance.card.id() == HiddenCard::ID));
+        assert!(player.deck.iter().all(|card| card.id() == HiddenCard::ID));
+    }
+}
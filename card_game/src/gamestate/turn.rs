@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// One of the two sides of the board
+/// The player is always the person sitting behind this screen, the opponent is the other side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Side {
+    Player,
+    Opponent,
+}
+
+impl Side {
+    /// Get the side that is sitting across the board from this side
+    pub fn other(self) -> Side {
+        match self {
+            Side::Player => Side::Opponent,
+            Side::Opponent => Side::Player,
+        }
+    }
+}
+
+/// The phases that a single turn goes through, in this order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Phase {
+    /// The active player's resources get refreshed
+    StartOfTurn,
+    /// The active player draws a card from their deck
+    Draw,
+    /// The active player can play cards from their hand
+    Main,
+    /// The active player can attack with their minions
+    Combat,
+    /// The turn is over and will be passed to the other side
+    End,
+}
+
+/// Keeps track of whose turn it is, how many turns have passed and what phase the current turn is in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TurnState {
+    /// The side that is currently taking their turn
+    pub active: Side,
+
+    /// The number of the current turn, starting at 1
+    /// Both players' turns are counted, so the side that started the game has the odd turns and the other side has
+    /// the even turns
+    pub number: u32,
+
+    /// The phase that the current turn is in
+    pub phase: Phase,
+}
+
+impl TurnState {
+    /// Create a new turn state where the given side is about to start the first turn
+    pub fn new(first: Side) -> TurnState {
+        TurnState {
+            active: first,
+            number: 1,
+            phase: Phase::StartOfTurn,
+        }
+    }
+}
+
+impl Default for TurnState {
+    /// Create a new turn state where the player is about to start the first turn
+    fn default() -> TurnState {
+        TurnState::new(Side::Player)
+    }
+}
@@ -0,0 +1,173 @@
+use super::{Action, GameResult, GameState, Player, Side, TurnState};
+use crate::cards::{Card, HiddenCard, ResourceType};
+use crate::CardInstance;
+
+/// A game as one side of the board is allowed to know it
+/// This is everything on the board and in the graveyards, and the hand and deck of that side. Of the other side's hand
+/// and deck, only the amount of cards is known
+/// The AI and the game client look at the game through a view, so they can't know more than a real player would
+/// A spectator's view shows the game from the side of `Side::Player`, but doesn't show the cards of either side
+#[derive(Clone, Copy)]
+pub struct PlayerView<'a> {
+    game_state: &'a GameState,
+    side: Side,
+    spectator: bool,
+}
+
+/// One side of the board, as it's seen through a `PlayerView`
+#[derive(Clone, Copy)]
+pub struct SideView<'a> {
+    player: &'a Player,
+
+    // Can the viewer see the cards in the hand and the deck of this side?
+    visible: bool,
+}
+
+impl GameState {
+    /// Get the game as the given side is allowed to know it
+    pub fn view(&self, side: Side) -> PlayerView<'_> {
+        PlayerView {
+            game_state: self,
+            side,
+            spectator: false,
+        }
+    }
+
+    /// Get the game as someone that is watching it is allowed to know it
+    pub fn spectator_view(&self) -> PlayerView<'_> {
+        PlayerView {
+            game_state: self,
+            side: Side::Player,
+            spectator: true,
+        }
+    }
+}
+
+impl<'a> PlayerView<'a> {
+    /// The side that is looking at the game
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Returns true if this is the view of a spectator, who can't see the cards of either side
+    pub fn is_spectator(&self) -> bool {
+        self.spectator
+    }
+
+    /// Get one side of the board. Only the viewer's own side shows their hand and deck
+    pub fn player(&self, side: Side) -> SideView<'a> {
+        SideView {
+            player: self.game_state.player_at(side),
+            visible: side == self.side && !self.spectator,
+        }
+    }
+
+    pub fn turn(&self) -> TurnState {
+        self.game_state.turn
+    }
+
+    /// See `GameState::result`
+    pub fn result(&self) -> Option<GameResult> {
+        self.game_state.result()
+    }
+
+    /// Every action that was applied since the start of the game
+    /// The actions only refer to cards by their index, so they don't show which cards the other side played
+    pub fn history(&self) -> &'a [Action] {
+        self.game_state.history()
+    }
+
+    /// Create a game that only holds what the viewer knows
+    /// The cards in the hand and deck of every side that the viewer can't see are replaced by `HiddenCard`s, so they
+    /// can't be played. This can be used to try out actions, without getting to know anything the viewer shouldn't know
+    pub fn to_game_state(&self) -> GameState {
+        let mut game_state = self.game_state.clone();
+        for side in &[Side::Player, Side::Opponent] {
+            if self.player(*side).visible {
+                continue;
+            }
+            let hidden = game_state.player_at_mut(*side);
+            for instance in &mut hidden.hand {
+                instance.card = Box::new(HiddenCard);
+            }
+            for card in hidden
+                .deck
+                .iter_mut()
+                .chain(hidden.original_deck.iter_mut())
+            {
+                *card = Box::new(HiddenCard);
+            }
+        }
+        game_state
+    }
+}
+
+impl<'a> SideView<'a> {
+    pub fn name(&self) -> &'a str {
+        &self.player.name
+    }
+
+    pub fn health(&self) -> i32 {
+        self.player.health
+    }
+
+    /// The total pool of resources, see `Player::resources`
+    pub fn resources(&self) -> &'a [(ResourceType, u8)] {
+        &self.player.resources
+    }
+
+    /// The resources that can still be spent this turn, see `Player::available_resources`
+    pub fn available_resources(&self) -> &'a [(ResourceType, u8)] {
+        &self.player.available_resources
+    }
+
+    /// See `Player::available_resource`
+    pub fn available_resource(&self, resource: ResourceType) -> u8 {
+        self.player.available_resource(resource)
+    }
+
+    /// The cards in the hand, or None if the viewer can't see them
+    pub fn hand(&self) -> Option<&'a [CardInstance]> {
+        if self.visible {
+            Some(&self.player.hand)
+        } else {
+            None
+        }
+    }
+
+    /// The ids of the cards in the hand, see `CardInstance::id`
+    /// Everyone can see how many cards are in a hand and when they move, so these are known even if the cards are not
+    pub fn hand_ids(&self) -> impl Iterator<Item = u32> + 'a {
+        self.player.hand.iter().map(|instance| instance.id)
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.player.hand.len()
+    }
+
+    /// The cards that are left in the deck, in the order they will be drawn, or None if the viewer can't see them
+    pub fn deck(&self) -> Option<&'a [Box<dyn Card>]> {
+        if self.visible {
+            Some(&self.player.deck)
+        } else {
+            None
+        }
+    }
+
+    pub fn deck_size(&self) -> usize {
+        self.player.deck.len()
+    }
+
+    pub fn field(&self) -> &'a [CardInstance] {
+        &self.player.field
+    }
+
+    pub fn graveyard(&self) -> &'a [CardInstance] {
+        &self.player.graveyard
+    }
+
+    /// See `Player::decked_out`
+    pub fn decked_out(&self) -> bool {
+        self.player.decked_out
+    }
+}
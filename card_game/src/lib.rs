@@ -0,0 +1,21 @@
+//! The rules of the card game, without anything that has to do with drawing the game on a screen
+//! This can be used by the game client, but also by simulations, servers and tests that run without a display
+#![cfg_attr(not(debug_assertions), deny(dead_code))]
+
+pub mod ai;
+pub mod args;
+mod card_instance;
+pub mod cards;
+pub mod constants;
+pub mod deck;
+pub mod gamestate;
+pub mod network;
+pub mod puzzle;
+pub mod replay;
+pub mod save;
+mod utils;
+
+#[cfg(test)]
+mod test_utils;
+
+pub use self::card_instance::CardInstance;
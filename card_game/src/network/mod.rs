@@ -0,0 +1,497 @@
+mod session;
+mod snapshot;
+mod spectators;
+
+pub use self::session::ServerSession;
+pub use self::snapshot::{CardSnapshot, PlayerSnapshot, Snapshot};
+pub use self::spectators::{accept_spectators, Spectators};
+
+use crate::cards::CardDatabase;
+use crate::deck::{Deck, DeckError, DeckFormat, DeckValidator};
+use crate::gamestate::{Action, Event, GameState, Player, RuleError, Side};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::{fmt, io, thread};
+
+/// The version of the network protocol. Two players can only play together if they use the same version
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// The longest message that is accepted from the other side, in bytes, which is far more than any real message needs
+/// The other side is closed when it sends a longer message, so it can't run us out of memory with a line that never
+/// ends
+const MAX_MESSAGE_LENGTH: u64 = 1024 * 1024;
+
+/// Everything that is sent between two players, or between a player and a server
+/// Two players only send each other the decks and the actions, never the state of the game, see `GameState::history`
+/// A server keeps the decks and hands secret instead, and sends every player a `Snapshot`
+/// Every message is sent as a single line of RON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Message {
+    /// The first message of the player that joins a game
+    Hello(PlayerInfo),
+    /// The answer to `Hello` if the game can start
+    /// This holds the other player, the rules that both decks follow and whether the joining player takes the first turn
+    Welcome {
+        opponent: PlayerInfo,
+        format: DeckFormat,
+        first: bool,
+    },
+    /// The answer to `Hello` if the game can not start, with the reason
+    Rejected(String),
+    /// An action that the sender applied to their game, as seen from the sender's side of the board
+    Action(Action),
+    /// The hash of the sender's game after they ended their turn, see `GameState::state_hash_for`
+    Checksum { turn: u32, hash: u64 },
+    /// The first message of someone that wants to watch a game, with the version of the protocol that they use
+    /// A server can run many games, so `game` picks the id of the game to watch. Without it, the newest game is picked
+    Spectate { version: u32, game: Option<u32> },
+    /// Sent by a server after every action, and as the answer to `Hello` once the game starts
+    /// This holds what happened because of the action and the game as the receiver is allowed to see it, both seen
+    /// from the receiver's side of the board. Spectators get the same updates, seen from the side of the first player
+    Update {
+        events: Vec<Event>,
+        snapshot: Box<Snapshot>,
+    },
+}
+
+/// A player as they are at the start of a network game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerInfo {
+    /// The version of the protocol that the player uses, see `PROTOCOL_VERSION`
+    pub version: u32,
+
+    pub name: String,
+
+    /// The ids of the cards in the player's deck, in order. See `Card::id`
+    pub deck: Vec<String>,
+}
+
+impl PlayerInfo {
+    /// Create the info of a player with the current protocol version
+    pub fn new(name: &str, deck: Vec<String>) -> PlayerInfo {
+        PlayerInfo {
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+            deck,
+        }
+    }
+
+    /// Returns an error if this player uses another version of the protocol
+    pub fn check_version(&self) -> Result<(), NetworkError> {
+        if self.version != PROTOCOL_VERSION {
+            return Err(NetworkError::WrongVersion(self.version));
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the deck of this player contains unknown cards or breaks the rules of the validator's format
+    pub fn check_deck(
+        &self,
+        database: &CardDatabase,
+        validator: &DeckValidator,
+    ) -> Result<(), NetworkError> {
+        let deck = Deck {
+            cards: self.deck.clone(),
+        };
+        deck.create_valid_cards(database, validator)
+            .map(|_| ())
+            .map_err(NetworkError::InvalidDeck)
+    }
+
+    /// Create a player with the deck of this player
+    /// The cards in the deck are created from the given card database
+    pub fn create_player(&self, database: &CardDatabase) -> Result<Player, NetworkError> {
+        let deck = Deck {
+            cards: self.deck.clone(),
+        };
+        let mut player = Player::new(&self.name);
+        player.original_deck = deck
+            .create_cards(database)
+            .map_err(NetworkError::InvalidDeck)?;
+        Ok(player)
+    }
+}
+
+/// Start a network game between us and the other player, as seen from our side of the board
+/// `first` is true if we take the first turn
+/// Both players have to start the game with the same decks, format and first player, or the games won't match
+pub fn create_game(
+    database: &CardDatabase,
+    us: &PlayerInfo,
+    them: &PlayerInfo,
+    format: DeckFormat,
+    first: bool,
+) -> Result<GameState, NetworkError> {
+    let mut game_state = GameState::new(us.create_player(database)?, them.create_player(database)?);
+    game_state.deck_validator = DeckValidator::new(format);
+    game_state.starting_side = if first { Side::Player } else { Side::Opponent };
+    game_state.start_game().map_err(NetworkError::InvalidGame)?;
+    Ok(game_state)
+}
+
+/// Wait for another player to join on the given listener, and start a game with them
+/// We take the first turn, and both decks have to follow our format
+/// If the other player can't play, they get a `Message::Rejected` with the reason
+/// Spectators that show up before the other player are turned away, see `accept_spectators`
+pub fn host(
+    listener: &TcpListener,
+    database: &CardDatabase,
+    us: &PlayerInfo,
+    format: DeckFormat,
+) -> Result<(Connection, GameState), NetworkError> {
+    let (mut connection, them) = loop {
+        let (stream, _) = listener.accept().map_err(NetworkError::Io)?;
+        let mut connection = Connection::new(stream)?;
+        match connection.receive()? {
+            Message::Hello(them) => break (connection, them),
+            Message::Spectate { .. } => {
+                let reason = "The game has not started yet".to_string();
+                // The spectator might already be gone, which doesn't matter to us
+                let _ = connection.send(&Message::Rejected(reason));
+            }
+            _ => return Err(NetworkError::UnexpectedMessage),
+        }
+    };
+    let game_state = them
+        .check_version()
+        .and_then(|_| create_game(database, us, &them, format.clone(), true));
+    match game_state {
+        Ok(game_state) => {
+            connection.send(&Message::Welcome {
+                opponent: us.clone(),
+                format,
+                first: false,
+            })?;
+            Ok((connection, game_state))
+        }
+        Err(e) => {
+            connection.send(&Message::Rejected(e.to_string()))?;
+            Err(e)
+        }
+    }
+}
+
+/// Join the game of the player or server at the given address
+/// The host decides the format and who takes the first turn
+pub fn join<A: ToSocketAddrs>(
+    address: A,
+    database: &CardDatabase,
+    us: &PlayerInfo,
+) -> Result<(Connection, GameState), NetworkError> {
+    let stream = TcpStream::connect(address).map_err(NetworkError::Io)?;
+    let mut connection = Connection::new(stream)?;
+    connection.send(&Message::Hello(us.clone()))?;
+    match connection.receive()? {
+        Message::Welcome {
+            opponent,
+            format,
+            first,
+        } => {
+            opponent.check_version()?;
+            let game_state = create_game(database, us, &opponent, format, first)?;
+            Ok((connection, game_state))
+        }
+        Message::Rejected(reason) => Err(NetworkError::Rejected(reason)),
+        _ => Err(NetworkError::UnexpectedMessage),
+    }
+}
+
+/// A connection to another player or a server, which sends and receives `Message`s
+/// Messages are received on a separate thread, so `try_receive` can be called every frame without blocking
+pub struct Connection {
+    stream: TcpStream,
+    incoming: Receiver<Result<Message, NetworkError>>,
+}
+
+impl Connection {
+    /// Start sending and receiving messages over the given stream
+    pub fn new(stream: TcpStream) -> Result<Connection, NetworkError> {
+        let mut reader = BufReader::new(stream.try_clone().map_err(NetworkError::Io)?);
+        let (sender, incoming) = mpsc::channel();
+        thread::spawn(move || loop {
+            let message = Connection::read_message(&mut reader);
+            if let Err(NetworkError::MessageTooLong) = message {
+                let _ = reader.get_ref().shutdown(Shutdown::Both);
+            }
+            let failed = message.is_err();
+            if sender.send(message).is_err() || failed {
+                return;
+            }
+        });
+        Ok(Connection { stream, incoming })
+    }
+
+    /// Read the next line from the other side, and turn it into a message
+    fn read_message(reader: &mut BufReader<TcpStream>) -> Result<Message, NetworkError> {
+        let mut line = String::new();
+        // One byte more than the limit is read, so a message of exactly the maximum length still fits with its newline
+        reader
+            .take(MAX_MESSAGE_LENGTH + 1)
+            .read_line(&mut line)
+            .map_err(NetworkError::Io)?;
+        if !line.ends_with('\n') {
+            return Err(if line.len() as u64 > MAX_MESSAGE_LENGTH {
+                NetworkError::MessageTooLong
+            } else {
+                NetworkError::Disconnected
+            });
+        }
+        ron::from_str(line.trim_end()).map_err(|e| NetworkError::Format(e.to_string()))
+    }
+
+    /// Send a message to the other side
+    pub fn send(&mut self, message: &Message) -> Result<(), NetworkError> {
+        let mut line = ron::to_string(message).map_err(|e| NetworkError::Format(e.to_string()))?;
+        line.push('\n');
+        self.stream
+            .write_all(line.as_bytes())
+            .map_err(NetworkError::Io)
+    }
+
+    /// Wait for the next message from the other side
+    pub fn receive(&self) -> Result<Message, NetworkError> {
+        self.incoming
+            .recv()
+            .unwrap_or(Err(NetworkError::Disconnected))
+    }
+
+    /// Get the next message from the other side, if one has arrived
+    pub fn try_receive(&self) -> Option<Result<Message, NetworkError>> {
+        match self.incoming.try_recv() {
+            Ok(message) => Some(message),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(NetworkError::Disconnected)),
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // This also stops the thread that receives messages
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Plays a game in lockstep with another player
+/// Every action that we apply is sent to the other player, and every action that they send is applied to our game
+/// After every turn, the player that ended the turn sends a checksum of their game, so both players can check that
+/// their games are still the same
+pub struct Lockstep {
+    connection: Connection,
+
+    // The number of actions in the history of the game that the other player already knows about
+    synced: usize,
+
+    // The turn number of the game after the last action that both players know about
+    // A turn can end in several ways, e.g. with `Action::EndTurn` or by advancing past the combat phase, so the
+    // checksum is sent whenever this changes
+    turn: u32,
+
+    // The turn number and hash of our game right after the other player ended their last turn
+    // We might already have taken actions when their checksum arrives, so it's compared to this instead of the game
+    expected_checksum: Option<(u32, u64)>,
+}
+
+impl Lockstep {
+    /// Start playing in lockstep over the given connection
+    /// The game has to be in the same state for both players, e.g. right after `create_game`
+    pub fn new(connection: Connection, game_state: &GameState) -> Lockstep {
+        Lockstep {
+            connection,
+            synced: game_state.history().len(),
+            turn: game_state.turn.number,
+            expected_checksum: None,
+        }
+    }
+
+    /// Send the actions that we applied to the game since the last update, and apply the actions that the other
+    /// player sent since then
+    /// Every action goes through `GameState::apply`, so the other player can't break the rules
+    /// Returns the actions of the other player that were applied, or an error if the games are not the same anymore
+    /// or the connection was lost
+    pub fn update(&mut self, game_state: &mut GameState) -> Result<Vec<Action>, NetworkError> {
+        let new_actions = game_state.history()[self.synced..].to_vec();
+        self.synced = game_state.history().len();
+        for action in &new_actions {
+            self.connection.send(&Message::Action(*action))?;
+        }
+        if game_state.turn.number != self.turn {
+            // We can't take actions after our turn ended, so the game is in the state that the other player will check
+            self.turn = game_state.turn.number;
+            self.connection.send(&Message::Checksum {
+                turn: game_state.turn.number,
+                hash: game_state.state_hash_for(Side::Player),
+            })?;
+        }
+
+        let mut applied = Vec::new();
+        while let Some(message) = self.connection.try_receive() {
+            match message? {
+                Message::Action(action) => {
+                    let action = action.mirrored();
+                    game_state
+                        .apply(Side::Opponent, action)
+                        .map_err(NetworkError::InvalidAction)?;
+                    self.synced = game_state.history().len();
+                    applied.push(action);
+                    if game_state.turn.number != self.turn {
+                        self.turn = game_state.turn.number;
+                        self.expected_checksum = Some((
+                            game_state.turn.number,
+                            game_state.state_hash_for(Side::Opponent),
+                        ));
+                    }
+                }
+                Message::Checksum { turn, hash } => {
+                    if self.expected_checksum.take() != Some((turn, hash)) {
+                        return Err(NetworkError::Desync(turn));
+                    }
+                }
+                _ => return Err(NetworkError::UnexpectedMessage),
+            }
+        }
+        Ok(applied)
+    }
+}
+
+/// The reasons why a network game can not be set up or continued
+#[derive(Debug)]
+pub enum NetworkError {
+    /// The connection could not be made, or sending or receiving failed
+    Io(io::Error),
+    /// A message could not be read or written
+    Format(String),
+    /// The other side closed the connection
+    Disconnected,
+    /// The other side sent a message that is longer than `MAX_MESSAGE_LENGTH`
+    MessageTooLong,
+    /// The other side uses another version of the protocol
+    WrongVersion(u32),
+    /// The other side refused to start the game, with the reason
+    Rejected(String),
+    /// A snapshot of the game contains a card id that does not exist
+    UnknownCard(String),
+    /// The deck of a player can not be played with
+    InvalidDeck(DeckError),
+    /// The game can not be started, e.g. because a deck is not valid
+    InvalidGame(RuleError),
+    /// The other side sent a message that doesn't make sense at this point
+    UnexpectedMessage,
+    /// The other side sent an action that is not allowed by the rules
+    InvalidAction(RuleError),
+    /// The games of both sides are not the same anymore after the given turn
+    Desync(u32),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetworkError::Io(e) => write!(f, "Network error: {}", e),
+            NetworkError::Format(e) => write!(f, "Invalid message: {}", e),
+            NetworkError::Disconnected => write!(f, "The other player disconnected"),
+            NetworkError::MessageTooLong => write!(
+                f,
+                "The other player sent a message of more than {} bytes",
+                MAX_MESSAGE_LENGTH
+            ),
+            NetworkError::WrongVersion(version) => write!(
+                f,
+                "The other player uses protocol version {}, but only version {} is supported",
+                version, PROTOCOL_VERSION
+            ),
+            NetworkError::Rejected(reason) => write!(f, "The game was refused: {}", reason),
+            NetworkError::UnknownCard(id) => write!(f, "Unknown card {:?}", id),
+            NetworkError::InvalidDeck(e) => write!(f, "Invalid deck: {}", e),
+            NetworkError::InvalidGame(e) => write!(f, "Could not start the game: {}", e),
+            NetworkError::UnexpectedMessage => {
+                write!(f, "The other player sent an unexpected message")
+            }
+            NetworkError::InvalidAction(e) => {
+                write!(
+                    f,
+                    "The other player sent an action that is not allowed: {}",
+                    e
+                )
+            }
+            NetworkError::Desync(turn) => write!(
+                f,
+                "The game of the other player is not the same as ours after turn {}",
+                turn
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_game, Connection, Lockstep, NetworkError, PlayerInfo, MAX_MESSAGE_LENGTH};
+    use crate::deck::DeckFormat;
+    use crate::gamestate::{Action, Side};
+    use crate::test_utils::{database, starter_deck};
+    use std::io::Write;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Create two streams that are connected to each other
+    fn connected_streams() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (server, client)
+    }
+
+    #[test]
+    fn message_that_is_too_long_closes_the_connection() {
+        let (server, mut client) = connected_streams();
+        let connection = Connection::new(server).unwrap();
+        // The line never ends, so it's only noticed because of the limit
+        let writer = thread::spawn(move || {
+            let line = vec![b'a'; MAX_MESSAGE_LENGTH as usize + 1];
+            let _ = client.write_all(&line);
+        });
+        assert!(matches!(
+            connection.receive(),
+            Err(NetworkError::MessageTooLong)
+        ));
+        writer.join().unwrap();
+    }
+
+    #[test]
+    fn checksum_is_compared_when_the_turn_ends_without_end_turn() {
+        let (host_stream, guest_stream) = connected_streams();
+
+        let database = database();
+        let host = PlayerInfo::new("Host", starter_deck().cards);
+        let guest = PlayerInfo::new("Guest", starter_deck().cards);
+        let format = DeckFormat::default();
+        let mut host_game = create_game(&database, &host, &guest, format.clone(), true).unwrap();
+        let mut guest_game = create_game(&database, &guest, &host, format, false).unwrap();
+        let mut host_lockstep = Lockstep::new(Connection::new(host_stream).unwrap(), &host_game);
+        let mut guest_lockstep = Lockstep::new(Connection::new(guest_stream).unwrap(), &guest_game);
+
+        // The games are not the same anymore, which only shows in the checksum
+        guest_game.player.health -= 1;
+
+        // Skip the start of the turn, the main phase and the combat phase, which passes the turn
+        for _ in 0..3 {
+            host_game.apply(Side::Player, Action::AdvancePhase).unwrap();
+        }
+        assert_eq!(host_game.turn.active, Side::Opponent);
+        host_lockstep.update(&mut host_game).unwrap();
+
+        let start = Instant::now();
+        let result = loop {
+            match guest_lockstep.update(&mut guest_game) {
+                Ok(_) => assert!(start.elapsed() < Duration::from_secs(5)),
+                Err(e) => break e,
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert!(matches!(result, NetworkError::Desync(2)));
+    }
+}
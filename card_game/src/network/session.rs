@@ -0,0 +1,98 @@
+use super::{Connection, Message, NetworkError, PlayerInfo, PROTOCOL_VERSION};
+use crate::cards::CardDatabase;
+use crate::gamestate::{Event, GameState};
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// Plays a game on a server, which owns the game and checks every action
+/// We only get to see a `Snapshot` of the game, so our game is replaced with the snapshot that the server sends after
+/// every action. Our own actions are still applied to our game right away, so the player doesn't have to wait for
+/// the server to see them
+/// A session can also watch a game on a server, or a game that another player hosts. Spectators only get snapshots
+/// and never send actions
+pub struct ServerSession {
+    connection: Connection,
+    database: CardDatabase,
+
+    // The number of actions in the history of our game that were already sent to the server
+    synced: usize,
+}
+
+impl ServerSession {
+    /// Join a game on the server at the given address
+    /// This waits until the server found another player to play against
+    pub fn join<A: ToSocketAddrs>(
+        address: A,
+        database: &CardDatabase,
+        us: &PlayerInfo,
+    ) -> Result<(ServerSession, GameState), NetworkError> {
+        ServerSession::connect(address, database, &Message::Hello(us.clone()))
+    }
+
+    /// Watch the game with the given id on the server at the given address, or the newest game if no id is given
+    /// This also works for a game that another player hosts, which only has one game to watch
+    pub fn spectate<A: ToSocketAddrs>(
+        address: A,
+        database: &CardDatabase,
+        game: Option<u32>,
+    ) -> Result<(ServerSession, GameState), NetworkError> {
+        let spectate = Message::Spectate {
+            version: PROTOCOL_VERSION,
+            game,
+        };
+        ServerSession::connect(address, database, &spectate)
+    }
+
+    /// Connect to the given address, send the first message and wait for the first snapshot of the game
+    fn connect<A: ToSocketAddrs>(
+        address: A,
+        database: &CardDatabase,
+        first_message: &Message,
+    ) -> Result<(ServerSession, GameState), NetworkError> {
+        let stream = TcpStream::connect(address).map_err(NetworkError::Io)?;
+        let mut connection = Connection::new(stream)?;
+        connection.send(first_message)?;
+        match connection.receive()? {
+            Message::Update { snapshot, .. } => {
+                let game_state = snapshot.create_game(database)?;
+                let session = ServerSession {
+                    connection,
+                    database: database.clone(),
+                    synced: 0,
+                };
+                Ok((session, game_state))
+            }
+            Message::Rejected(reason) => Err(NetworkError::Rejected(reason)),
+            _ => Err(NetworkError::UnexpectedMessage),
+        }
+    }
+
+    /// Send the actions that we applied to the game since the last update, and replace the game with the latest
+    /// snapshot from the server
+    /// If the server did not accept one of our actions, it sends a snapshot without events to undo it
+    /// Returns the events that happened since the last update, or None if the server sent nothing new
+    pub fn update(
+        &mut self,
+        game_state: &mut GameState,
+    ) -> Result<Option<Vec<Event>>, NetworkError> {
+        for action in &game_state.history()[self.synced..] {
+            self.connection.send(&Message::Action(*action))?;
+        }
+        self.synced = game_state.history().len();
+
+        let mut events = None;
+        while let Some(message) = self.connection.try_receive() {
+            match message? {
+                Message::Update {
+                    events: new_events,
+                    snapshot,
+                } => {
+                    *game_state = snapshot.create_game(&self.database)?;
+                    self.synced = 0;
+                    events.get_or_insert_with(Vec::new).extend(new_events);
+                }
+                _ => return Err(NetworkError::UnexpectedMessage),
+            }
+        }
+        Ok(events)
+    }
+}
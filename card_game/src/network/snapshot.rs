@@ -0,0 +1,236 @@
+use super::NetworkError;
+use crate::cards::{Card, CardDatabase, HiddenCard, ResourceType};
+use crate::gamestate::{GameState, Player, PlayerView, Side, SideView, TurnState};
+use crate::CardInstance;
+use serde::{Deserialize, Serialize};
+
+/// The part of a game that one side of the board is allowed to see, as a server sends it to that side
+/// The snapshot is seen from the side that it was made for, so that side is always `Side::Player`
+/// The cards in the opponent's hand are hidden, and of both decks only the amount of cards is known
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub player: PlayerSnapshot,
+    pub opponent: PlayerSnapshot,
+    pub turn: TurnState,
+}
+
+/// One side of the board in a `Snapshot`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub name: String,
+    pub health: i32,
+    pub resources: Vec<(ResourceType, u8)>,
+    pub available_resources: Vec<(ResourceType, u8)>,
+
+    /// The cards in the hand. If the hand is hidden, every card is a `CardSnapshot::hidden`
+    pub hand: Vec<CardSnapshot>,
+
+    /// The amount of cards that are left in the deck. The deck itself is never sent, so its order stays a secret
+    pub deck_size: usize,
+
+    pub field: Vec<CardSnapshot>,
+    pub graveyard: Vec<CardSnapshot>,
+    pub decked_out: bool,
+}
+
+/// A single card in a `Snapshot`, with the stats that it has right now
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardSnapshot {
+    /// The id of the card instance, see `CardInstance::id`
+    pub instance: u32,
+
+    /// The id of the card, see `Card::id`. This is `HiddenCard::ID` if the card is hidden
+    pub card: String,
+
+    pub attack: Option<u8>,
+    pub health: Option<u8>,
+    pub summoning_sick: bool,
+    pub has_attacked: bool,
+}
+
+impl Snapshot {
+    /// Take a snapshot of everything in the view
+    pub fn new(view: PlayerView) -> Snapshot {
+        let side = view.side();
+        let mut turn = view.turn();
+        turn.active = if turn.active == side {
+            Side::Player
+        } else {
+            Side::Opponent
+        };
+        Snapshot {
+            player: PlayerSnapshot::new(view.player(side)),
+            opponent: PlayerSnapshot::new(view.player(side.other())),
+            turn,
+        }
+    }
+
+    /// Create a game that looks like this snapshot
+    /// Hidden cards and the cards in the decks are replaced by a `HiddenCard`, so the game can't continue further
+    /// than the next card that's drawn. Use it to show the game, and wait for the next snapshot after every action
+    pub fn create_game(&self, database: &CardDatabase) -> Result<GameState, NetworkError> {
+        let mut game_state = GameState::new(
+            self.player.create_player(database)?,
+            self.opponent.create_player(database)?,
+        );
+        game_state.turn = self.turn;
+        Ok(game_state)
+    }
+}
+
+impl PlayerSnapshot {
+    /// Take a snapshot of one side of the board, with the cards in the hand hidden if the view can't see them
+    fn new(player: SideView) -> PlayerSnapshot {
+        let hand = match player.hand() {
+            Some(hand) => hand.iter().map(CardSnapshot::new).collect(),
+            None => player.hand_ids().map(CardSnapshot::hidden).collect(),
+        };
+        PlayerSnapshot {
+            name: player.name().to_string(),
+            health: player.health(),
+            resources: player.resources().to_vec(),
+            available_resources: player.available_resources().to_vec(),
+            hand,
+            deck_size: player.deck_size(),
+            field: player.field().iter().map(CardSnapshot::new).collect(),
+            graveyard: player.graveyard().iter().map(CardSnapshot::new).collect(),
+            decked_out: player.decked_out(),
+        }
+    }
+
+    fn create_player(&self, database: &CardDatabase) -> Result<Player, NetworkError> {
+        let create_instances = |cards: &[CardSnapshot]| {
+            cards
+                .iter()
+                .map(|card| card.create_instance(database))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let mut player = Player::new(&self.name);
+        player.health = self.health;
+        player.resources = self.resources.clone();
+        player.available_resources = self.available_resources.clone();
+        player.hand = create_instances(&self.hand)?;
+        player.field = create_instances(&self.field)?;
+        player.graveyard = create_instances(&self.graveyard)?;
+        player.decked_out = self.decked_out;
+        for _ in 0..self.deck_size {
+            player.deck.push(Box::new(HiddenCard));
+        }
+        // Cards that are drawn before the next snapshot arrives get new ids, which can't be the same as an existing one
+        let next_card_id = self
+            .hand
+            .iter()
+            .chain(&self.field)
+            .chain(&self.graveyard)
+            .map(|card| card.instance + 1)
+            .max()
+            .unwrap_or(0);
+        player.set_next_card_id(next_card_id);
+        Ok(player)
+    }
+}
+
+impl CardSnapshot {
+    /// Take a snapshot of a card that can be seen
+    pub fn new(instance: &CardInstance) -> CardSnapshot {
+        CardSnapshot {
+            instance: instance.id,
+            card: instance.card.id().to_string(),
+            attack: instance.card.attack().cloned(),
+            health: instance.card.health().cloned(),
+            summoning_sick: instance.summoning_sick,
+            has_attacked: instance.has_attacked,
+        }
+    }
+
+    /// Take a snapshot of a card in a hand that can not be seen, which only holds the id of the instance
+    pub fn hidden(instance: u32) -> CardSnapshot {
+        CardSnapshot {
+            instance,
+            card: HiddenCard::ID.to_string(),
+            attack: None,
+            health: None,
+            summoning_sick: true,
+            has_attacked: false,
+        }
+    }
+
+    /// Create the card instance that this snapshot was taken of
+    pub fn create_instance(&self, database: &CardDatabase) -> Result<CardInstance, NetworkError> {
+        let card: Box<dyn Card> = if self.card == HiddenCard::ID {
+            Box::new(HiddenCard)
+        } else {
+            database
+                .create_card_with_stats(&self.card, self.attack, self.health)
+                .ok_or_else(|| NetworkError::UnknownCard(self.card.clone()))?
+        };
+        let mut instance = CardInstance::new(self.instance, card);
+        instance.summoning_sick = self.summoning_sick;
+        instance.has_attacked = self.has_attacked;
+        Ok(instance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Snapshot;
+    use crate::cards::HiddenCard;
+    use crate::gamestate::Side;
+    use crate::test_utils::{database, new_game, play_actions};
+
+    #[test]
+    fn snapshot_hides_the_opponents_hand_and_both_decks() {
+        let mut game_state = new_game();
+        play_actions(&mut game_state, 10);
+        // The snapshot for the opponent is seen from their side of the board
+        let snapshot = Snapshot::new(game_state.view(Side::Opponent));
+        assert_eq!(snapshot.player.name, game_state.opponent.name);
+        assert!(snapshot
+            .player
+            .hand
+            .iter()
+            .all(|card| card.card != HiddenCard::ID));
+        assert_eq!(snapshot.opponent.hand.len(), game_state.player.hand.len());
+        assert!(snapshot
+            .opponent
+            .hand
+            .iter()
+            .all(|card| card.card == HiddenCard::ID && card.attack.is_none()));
+
+        // The order of the decks is never sent, only their size
+        let created = snapshot.create_game(&database()).unwrap();
+        assert_eq!(created.opponent.deck.len(), game_state.player.deck.len());
+        for side in &[Side::Player, Side::Opponent] {
+            let player = created.player_at(*side);
+            assert!(player.deck.iter().all(|card| card.id() == HiddenCard::ID));
+        }
+    }
+
+    #[test]
+    fn spectator_snapshot_hides_both_hands() {
+        let game_state = new_game();
+        let snapshot = Snapshot::new(game_state.spectator_view());
+        let created = snapshot.create_game(&database()).unwrap();
+        for side in &[Side::Player, Side::Opponent] {
+            assert!(created
+                .player_at(*side)
+                .hand
+                .iter()
+                .all(|instance| instance.card.id() == HiddenCard::ID));
+        }
+    }
+
+    #[test]
+    fn cards_drawn_after_a_snapshot_get_new_ids() {
+        let mut game_state = new_game();
+        play_actions(&mut game_state, 40);
+        let snapshot = Snapshot::new(game_state.view(Side::Player));
+        let created = snapshot.create_game(&database()).unwrap();
+        for side in &[Side::Player, Side::Opponent] {
+            assert_eq!(
+                created.player_at(*side).next_card_id(),
+                game_state.player_at(*side).next_card_id()
+            );
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use super::{Connection, Message, NetworkError, Snapshot, PROTOCOL_VERSION};
+use crate::gamestate::{Event, GameState};
+use std::collections::VecDeque;
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Sends a game to everyone that watches it
+/// Spectators only see what's public, so neither hand is shown. Every update can be held back for a while, so
+/// spectators can't tell the players what the other player just did
+pub struct Spectators {
+    // How long every update is held back
+    delay: Duration,
+
+    // Spectators that want to start watching, see `Spectators::new`
+    joining: Receiver<Connection>,
+
+    watching: Vec<Connection>,
+
+    // The updates that are held back, together with the time that they can be sent
+    pending: VecDeque<(Instant, Message)>,
+
+    // The last update that was sent, which new spectators get first
+    latest: Option<Message>,
+
+    // The number of actions in the history of the game when it was last recorded
+    recorded_actions: usize,
+}
+
+impl Spectators {
+    /// Create an empty list of spectators, who will see every update of the game `delay` after it happened
+    /// New spectators can be sent through the returned sender, e.g. by `accept_spectators`
+    pub fn new(delay: Duration) -> (Spectators, Sender<Connection>) {
+        let (sender, joining) = mpsc::channel();
+        let spectators = Spectators {
+            delay,
+            joining,
+            watching: Vec::new(),
+            pending: VecDeque::new(),
+            latest: None,
+            recorded_actions: 0,
+        };
+        (spectators, sender)
+    }
+
+    /// Remember the game as it is right now, with the events that led to it
+    /// The spectators get to see it once the delay has passed, see `update`
+    pub fn record(&mut self, events: &[Event], game_state: &GameState) {
+        let message = Message::Update {
+            events: events.to_vec(),
+            snapshot: Box::new(Snapshot::new(game_state.spectator_view())),
+        };
+        self.pending
+            .push_back((Instant::now() + self.delay, message));
+        self.recorded_actions = game_state.history().len();
+    }
+
+    /// Record the game if an action was applied to it since it was last recorded
+    /// This is for games where the events are not known, like a game that is played in lockstep
+    pub fn record_changes(&mut self, game_state: &GameState) {
+        if game_state.history().len() != self.recorded_actions {
+            self.record(&[], game_state);
+        }
+    }
+
+    /// Let new spectators start watching, and send every update whose delay has passed
+    /// Spectators that can't be reached anymore stop watching
+    pub fn update(&mut self) {
+        while let Ok(mut connection) = self.joining.try_recv() {
+            let sent = match self.latest {
+                Some(ref latest) => connection.send(latest).is_ok(),
+                None => true,
+            };
+            if sent {
+                self.watching.push(connection);
+            }
+        }
+
+        let now = Instant::now();
+        while self.pending.front().is_some_and(|(time, _)| *time <= now) {
+            if let Some((_, message)) = self.pending.pop_front() {
+                self.watching
+                    .retain_mut(|connection| connection.send(&message).is_ok());
+                self.latest = Some(message);
+            }
+        }
+    }
+
+    /// Returns true if every update was sent
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Accept spectators on the given listener on a separate thread, e.g. after `host` found the other player
+/// Everyone that sends a `Message::Spectate` with the same protocol version is sent into `spectators`, everyone else
+/// gets a `Message::Rejected`
+pub fn accept_spectators(listener: TcpListener, spectators: Sender<Connection>) {
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let spectators = spectators.clone();
+            let greet = move || -> Result<(), NetworkError> {
+                let mut connection = Connection::new(stream.map_err(NetworkError::Io)?)?;
+                let reason = match connection.receive()? {
+                    Message::Spectate { version, .. } if version != PROTOCOL_VERSION => {
+                        NetworkError::WrongVersion(version).to_string()
+                    }
+                    Message::Spectate { .. } => {
+                        return spectators
+                            .send(connection)
+                            .map_err(|_| NetworkError::Disconnected);
+                    }
+                    _ => "This game already has two players".to_string(),
+                };
+                connection.send(&Message::Rejected(reason))
+            };
+            // Every spectator is greeted on their own thread, so a slow spectator can't keep others waiting
+            thread::spawn(greet);
+        }
+    });
+}
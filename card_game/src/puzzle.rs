@@ -0,0 +1,270 @@
+use crate::cards::{CardDatabase, ResourceType};
+use crate::constants::{MAX_FIELD_SIZE, STARTING_HEALTH};
+use crate::gamestate::{Action, GameResult, GameState, Phase, Player, Side, TurnState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::{fmt, fs, io, path::Path};
+
+/// A board that the player has to win from in a single turn
+/// The game has no randomness, so every puzzle has a fixed set of solutions
+/// See `assets/puzzles` for examples of the file format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Puzzle {
+    /// The name of the puzzle as it's shown to the player
+    pub name: String,
+
+    /// Explains what the player has to do, if anything more than winning this turn
+    #[serde(default)]
+    pub description: String,
+
+    pub player: PuzzlePlayer,
+    pub opponent: PuzzlePlayer,
+}
+
+/// One side of the board of a puzzle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PuzzlePlayer {
+    #[serde(default = "default_health")]
+    pub health: i32,
+
+    /// The resources that can be spent this turn
+    #[serde(default)]
+    pub resources: Vec<(ResourceType, u8)>,
+
+    /// The ids of the cards in the hand, see `Card::id`
+    #[serde(default)]
+    pub hand: Vec<String>,
+
+    /// The minions on the field, from left to right
+    #[serde(default)]
+    pub field: Vec<PuzzleMinion>,
+
+    /// The ids of the cards in the deck, in the order that they will be drawn
+    #[serde(default)]
+    pub deck: Vec<String>,
+}
+
+/// A minion on the field of a puzzle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PuzzleMinion {
+    /// The id of the card of the minion
+    pub card: String,
+
+    /// The current attack and health of the minion, if they are not the same as on the card
+    #[serde(default)]
+    pub attack: Option<u8>,
+    #[serde(default)]
+    pub health: Option<u8>,
+
+    /// Was the minion played this turn? Minions that are summoning sick can not attack
+    #[serde(default)]
+    pub summoning_sick: bool,
+}
+
+fn default_health() -> i32 {
+    STARTING_HEALTH
+}
+
+/// How far the player is with solving a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleStatus {
+    /// The player can still win this turn
+    Unsolved,
+    /// The player won the game
+    Solved,
+    /// The player ended their turn without winning, or lost the game
+    Failed,
+}
+
+impl Puzzle {
+    /// Load a puzzle from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Puzzle, PuzzleError> {
+        let contents = fs::read_to_string(path).map_err(PuzzleError::Io)?;
+        ron::from_str(&contents).map_err(|e| PuzzleError::Format(e.to_string()))
+    }
+
+    /// Create the game of this puzzle, with the player at the start of their main phase
+    /// The cards are created from the given card database
+    pub fn create_game(&self, database: &CardDatabase) -> Result<GameState, PuzzleError> {
+        let mut game_state = GameState::new(
+            self.player.create_player("Player", database)?,
+            self.opponent.create_player("Opponent", database)?,
+        );
+        game_state.turn = TurnState {
+            active: Side::Player,
+            number: 1,
+            phase: Phase::Main,
+        };
+        Ok(game_state)
+    }
+}
+
+impl PuzzlePlayer {
+    fn create_player(&self, name: &str, database: &CardDatabase) -> Result<Player, PuzzleError> {
+        let create_card = |id: &String| {
+            database
+                .create_card(id)
+                .ok_or_else(|| PuzzleError::UnknownCard(id.clone()))
+        };
+        let mut player = Player::new(name);
+        player.health = self.health;
+        player.resources = self.resources.clone();
+        player.refresh_resources();
+        for id in &self.deck {
+            player.deck.push(create_card(id)?);
+        }
+        for id in &self.hand {
+            let instance = player.create_instance(create_card(id)?);
+            player.hand.push(instance);
+        }
+        if self.field.len() > MAX_FIELD_SIZE {
+            return Err(PuzzleError::TooManyMinions(self.field.len()));
+        }
+        for minion in &self.field {
+            // The stats are changed the same way as in saved games, see `CardDatabase::create_card_with_stats`
+            let card = database
+                .create_card_with_stats(&minion.card, minion.attack, minion.health)
+                .ok_or_else(|| PuzzleError::UnknownCard(minion.card.clone()))?;
+            if card.health().is_none() {
+                return Err(PuzzleError::InvalidMinion {
+                    id: minion.card.clone(),
+                    reason: "only minions can be put on the field".to_string(),
+                });
+            }
+            let mut instance = player.create_instance(card);
+            instance.summoning_sick = minion.summoning_sick;
+            player.field.push(instance);
+        }
+        Ok(player)
+    }
+}
+
+/// Check if the player solved the puzzle in the given game
+/// A puzzle has to be won in the turn that it starts in, so the puzzle fails as soon as the player's turn is over
+pub fn status(game_state: &GameState) -> PuzzleStatus {
+    match game_state.result() {
+        Some(GameResult::Winner(Side::Player)) => PuzzleStatus::Solved,
+        Some(_) => PuzzleStatus::Failed,
+        None if game_state.turn.active != Side::Player => PuzzleStatus::Failed,
+        None => PuzzleStatus::Unsolved,
+    }
+}
+
+/// Find the actions that solve the puzzle from the given game, by trying every order of actions
+/// Returns None if the puzzle can't be solved anymore
+pub fn solve(game_state: &GameState) -> Option<Vec<Action>> {
+    let mut visited = HashSet::new();
+    let mut solution = solve_from(game_state, &mut visited)?;
+    solution.reverse();
+    Some(solution)
+}
+
+/// Search for a solution, skipping every state that was already tried
+/// The solution is returned in reverse order, so every step only has to push its own action
+fn solve_from(game_state: &GameState, visited: &mut HashSet<u64>) -> Option<Vec<Action>> {
+    match status(game_state) {
+        PuzzleStatus::Solved => return Some(Vec::new()),
+        PuzzleStatus::Failed => return None,
+        PuzzleStatus::Unsolved => {}
+    }
+    if !visited.insert(game_state.state_hash()) {
+        return None;
+    }
+    for action in game_state.legal_actions() {
+        // Ending the turn fails the puzzle, and advancing the phase only takes options away
+        if action == Action::EndTurn || action == Action::AdvancePhase {
+            continue;
+        }
+        let mut next = game_state.clone();
+        if next.apply(Side::Player, action).is_err() {
+            continue;
+        }
+        if let Some(mut solution) = solve_from(&next, visited) {
+            solution.push(action);
+            return Some(solution);
+        }
+    }
+    None
+}
+
+/// The reasons why a puzzle can not be loaded
+#[derive(Debug)]
+pub enum PuzzleError {
+    /// The puzzle file could not be read
+    Io(io::Error),
+    /// The puzzle file is not a valid puzzle
+    Format(String),
+    /// The puzzle contains a card id that does not exist
+    UnknownCard(String),
+    /// The puzzle has more minions on one side of the field than the rules allow
+    TooManyMinions(usize),
+    /// A minion on the field can not be set up the way the puzzle describes
+    InvalidMinion { id: String, reason: String },
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PuzzleError::Io(e) => write!(f, "Could not read the puzzle file: {}", e),
+            PuzzleError::Format(e) => write!(f, "Invalid puzzle file: {}", e),
+            PuzzleError::UnknownCard(id) => write!(f, "Unknown card {:?}", id),
+            PuzzleError::TooManyMinions(count) => write!(
+                f,
+                "The puzzle has {} minions on one side, but only {} fit on the field",
+                count, MAX_FIELD_SIZE
+            ),
+            PuzzleError::InvalidMinion { id, reason } => {
+                write!(f, "Minion {:?} is not valid: {}", id, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve, status, Puzzle, PuzzleStatus};
+    use crate::gamestate::Side;
+    use crate::test_utils::{database, ASSETS};
+    use std::fs;
+
+    #[test]
+    fn every_puzzle_in_the_assets_can_be_solved() {
+        let database = database();
+        let files: Vec<_> = fs::read_dir(format!("{}/puzzles", ASSETS))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+            .collect();
+        assert!(!files.is_empty());
+        for file in files {
+            let mut game_state = Puzzle::load(&file)
+                .and_then(|puzzle| puzzle.create_game(&database))
+                .unwrap();
+            let solution = solve(&game_state)
+                .unwrap_or_else(|| panic!("{} can not be solved", file.display()));
+            for action in solution {
+                game_state.apply(Side::Player, action).unwrap();
+            }
+            assert_eq!(status(&game_state), PuzzleStatus::Solved);
+        }
+    }
+
+    #[test]
+    fn minion_stats_are_overridden_like_in_saved_games() {
+        let puzzle: Puzzle = ron::from_str(
+            r#"(
+                name: "Stats",
+                player: (field: [(card: "light_elemental", attack: Some(4), health: Some(3))]),
+                opponent: (),
+            )"#,
+        )
+        .unwrap();
+        let game_state = puzzle.create_game(&database()).unwrap();
+        // The attack of a light elemental is always the same as its health
+        let card = &game_state.player.field[0].card;
+        assert_eq!(card.attack(), Some(&3));
+        assert_eq!(card.health(), Some(&3));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::cards::CardDatabase;
+use crate::deck::{Deck, DeckError, DeckFormat, DeckValidator};
+use crate::gamestate::{Action, GameState, Player, RuleError, Side};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, io, path::Path};
+
+/// The version of the replay format. Replays with another version can not be loaded
+pub const REPLAY_VERSION: u32 = 1;
+
+/// A recording of a complete game
+/// The decks that both players started with and the actions that were applied are enough to reproduce the exact same
+/// game, see `GameState::history`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub player: ReplayPlayer,
+    pub opponent: ReplayPlayer,
+    pub actions: Vec<Action>,
+
+    // The deck format that the game was played in
+    #[serde(default)]
+    pub format: DeckFormat,
+
+    // The side that took the first turn
+    #[serde(default = "default_starting_side")]
+    pub starting_side: Side,
+}
+
+fn default_starting_side() -> Side {
+    Side::Player
+}
+
+/// A player as they were at the start of a recorded game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayPlayer {
+    pub name: String,
+
+    // The ids of the cards in the deck, in order. See `Card::id`
+    pub deck: Vec<String>,
+}
+
+impl ReplayPlayer {
+    fn new(player: &Player) -> ReplayPlayer {
+        ReplayPlayer {
+            name: player.name.clone(),
+            deck: Deck::from_cards(&player.original_deck).cards,
+        }
+    }
+
+    fn create_player(&self, database: &CardDatabase) -> Result<Player, ReplayError> {
+        let deck = Deck {
+            cards: self.deck.clone(),
+        };
+        let mut player = Player::new(&self.name);
+        player.original_deck = deck
+            .create_cards(database)
+            .map_err(ReplayError::InvalidDeck)?;
+        Ok(player)
+    }
+}
+
+impl Replay {
+    /// Record the decks of the given game, and all the actions that were applied since the game started
+    pub fn new(game_state: &GameState) -> Replay {
+        Replay {
+            version: REPLAY_VERSION,
+            player: ReplayPlayer::new(&game_state.player),
+            opponent: ReplayPlayer::new(&game_state.opponent),
+            actions: game_state.history().to_vec(),
+            format: game_state.deck_validator.format.clone(),
+            starting_side: game_state.starting_side,
+        }
+    }
+
+    /// Load a replay from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Replay, ReplayError> {
+        let contents = fs::read_to_string(path).map_err(ReplayError::Io)?;
+        let replay: Replay =
+            ron::from_str(&contents).map_err(|e| ReplayError::Format(e.to_string()))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::WrongVersion(replay.version));
+        }
+        Ok(replay)
+    }
+
+    /// Save the replay to the given file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ReplayError> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| ReplayError::Format(e.to_string()))?;
+        fs::write(path, contents).map_err(ReplayError::Io)
+    }
+
+    /// Start a new game with the decks of this replay, without applying any actions
+    /// The cards in the decks are created from the given card database
+    pub fn create_game(&self, database: &CardDatabase) -> Result<GameState, ReplayError> {
+        let mut game_state = GameState::new(
+            self.player.create_player(database)?,
+            self.opponent.create_player(database)?,
+        );
+        game_state.deck_validator = DeckValidator::new(self.format.clone());
+        game_state.starting_side = self.starting_side;
+        game_state.start_game().map_err(ReplayError::InvalidGame)?;
+        Ok(game_state)
+    }
+
+    /// Create the game as it was after the first `step` actions were applied
+    pub fn game_at(&self, database: &CardDatabase, step: usize) -> Result<GameState, ReplayError> {
+        let mut game_state = self.create_game(database)?;
+        for index in 0..step.min(self.actions.len()) {
+            self.apply_step(&mut game_state, index)?;
+        }
+        Ok(game_state)
+    }
+
+    /// Apply the action at index `step` to the given game
+    /// The game has to be in the state right before that action, see `game_at`
+    pub fn apply_step(&self, game_state: &mut GameState, step: usize) -> Result<(), ReplayError> {
+        let action = *self.actions.get(step).ok_or(ReplayError::EndOfReplay)?;
+        game_state
+            .apply(game_state.turn.active, action)
+            .map(|_| ())
+            .map_err(|error| ReplayError::InvalidAction { step, error })
+    }
+}
+
+/// The reasons why a replay can not be loaded, saved or played back
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The replay file could not be read or written
+    Io(io::Error),
+    /// The replay file is not a valid replay
+    Format(String),
+    /// The replay was made with another version of the replay format
+    WrongVersion(u32),
+    /// One of the decks can not be created, e.g. because it contains a card id that does not exist
+    InvalidDeck(DeckError),
+    /// The game of the replay can not be started, e.g. because a deck is not valid
+    InvalidGame(RuleError),
+    /// There are no more actions in the replay
+    EndOfReplay,
+    /// An action in the replay is not allowed by the rules. This means the replay does not belong to these decks
+    InvalidAction { step: usize, error: RuleError },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "Could not access the replay file: {}", e),
+            ReplayError::Format(e) => write!(f, "Invalid replay file: {}", e),
+            ReplayError::WrongVersion(version) => write!(
+                f,
+                "Replay has version {}, but only version {} is supported",
+                version, REPLAY_VERSION
+            ),
+            ReplayError::InvalidDeck(e) => write!(f, "Invalid deck: {}", e),
+            ReplayError::InvalidGame(e) => write!(f, "Could not start the game: {}", e),
+            ReplayError::EndOfReplay => write!(f, "The replay has no more actions"),
+            ReplayError::InvalidAction { step, error } => {
+                write!(f, "Action {} of the replay is not allowed: {}", step, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Replay, ReplayError};
+    use crate::gamestate::Action;
+    use crate::test_utils::{database, new_game, play_actions};
+
+    #[test]
+    fn replay_plays_back_the_same_game() {
+        let mut game_state = new_game();
+        play_actions(&mut game_state, 100);
+
+        let file = std::env::temp_dir().join(format!("replay_test_{}.ron", std::process::id()));
+        Replay::new(&game_state).save(&file).unwrap();
+        let replay = Replay::load(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let played_back = replay.game_at(&database(), usize::MAX).unwrap();
+        assert_eq!(played_back.history(), game_state.history());
+        assert_eq!(played_back.state_hash(), game_state.state_hash());
+    }
+
+    #[test]
+    fn replay_can_jump_to_any_step() {
+        let mut game_state = new_game();
+        play_actions(&mut game_state, 30);
+        let replay = Replay::new(&game_state);
+        let database = database();
+
+        let mut expected = replay.create_game(&database).unwrap();
+        for step in 0..replay.actions.len() {
+            assert_eq!(
+                replay.game_at(&database, step).unwrap().state_hash(),
+                expected.state_hash()
+            );
+            replay.apply_step(&mut expected, step).unwrap();
+        }
+        assert!(matches!(
+            replay.apply_step(&mut expected, replay.actions.len()),
+            Err(ReplayError::EndOfReplay)
+        ));
+    }
+
+    #[test]
+    fn replay_that_does_not_match_the_rules_is_rejected() {
+        let mut game_state = new_game();
+        play_actions(&mut game_state, 10);
+        let mut replay = Replay::new(&game_state);
+        replay.actions[5] = Action::PlayCard { from: 99, to: 0 };
+        assert!(matches!(
+            replay.game_at(&database(), usize::MAX),
+            Err(ReplayError::InvalidAction { step: 5, .. })
+        ));
+
+        replay.player.deck[0] = "missing_card".to_string();
+        assert!(matches!(
+            replay.create_game(&database()),
+            Err(ReplayError::InvalidDeck(_))
+        ));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::cards::{Card, CardDatabase, ResourceType};
+use crate::deck::{DeckFormat, DeckValidator};
+use crate::gamestate::{Action, GameState, Player, Side, TurnState};
+use crate::CardInstance;
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, io, path::Path};
+
+/// The version of the save format. Saves with another version can not be loaded
+pub const SAVE_VERSION: u32 = 1;
+
+/// A complete game as it is right now, so it can be continued later
+/// Unlike a `Replay`, this holds every card on the board with its current stats, so the game doesn't have to be
+/// played again from the start when it's loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    pub player: SavedPlayer,
+    pub opponent: SavedPlayer,
+    pub turn: TurnState,
+
+    // The deck format that the game is played in
+    pub format: DeckFormat,
+
+    // The side that took the first turn
+    pub starting_side: Side,
+
+    // Every action that was applied since the start of the game, so a replay can still be made of the game
+    pub history: Vec<Action>,
+}
+
+/// One side of the board in a `SavedGame`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub name: String,
+    pub health: i32,
+    pub resources: Vec<(ResourceType, u8)>,
+    pub available_resources: Vec<(ResourceType, u8)>,
+
+    /// The ids of the cards that were in the deck when the game started, in order. See `Card::id`
+    pub original_deck: Vec<String>,
+
+    /// The cards that are left in the deck, in the order that they will be drawn
+    pub deck: Vec<SavedCard>,
+
+    pub hand: Vec<SavedInstance>,
+    pub field: Vec<SavedInstance>,
+    pub graveyard: Vec<SavedInstance>,
+    pub decked_out: bool,
+
+    /// The id that the next card instance of this player will get
+    pub next_card_id: u32,
+}
+
+/// A single card in a `SavedGame`, with the stats that it has right now
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedCard {
+    /// The id of the card, see `Card::id`
+    pub id: String,
+
+    pub attack: Option<u8>,
+    pub health: Option<u8>,
+}
+
+/// A card instance in a `SavedGame`, see `CardInstance`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedInstance {
+    pub id: u32,
+    pub card: SavedCard,
+    pub summoning_sick: bool,
+    pub has_attacked: bool,
+}
+
+impl SavedGame {
+    /// Save everything about the given game
+    pub fn new(game_state: &GameState) -> SavedGame {
+        SavedGame {
+            version: SAVE_VERSION,
+            player: SavedPlayer::new(&game_state.player),
+            opponent: SavedPlayer::new(&game_state.opponent),
+            turn: game_state.turn,
+            format: game_state.deck_validator.format.clone(),
+            starting_side: game_state.starting_side,
+            history: game_state.history().to_vec(),
+        }
+    }
+
+    /// Load a saved game from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<SavedGame, SaveError> {
+        let contents = fs::read_to_string(path).map_err(SaveError::Io)?;
+        let saved: SavedGame =
+            ron::from_str(&contents).map_err(|e| SaveError::Format(e.to_string()))?;
+        if saved.version != SAVE_VERSION {
+            return Err(SaveError::WrongVersion(saved.version));
+        }
+        Ok(saved)
+    }
+
+    /// Save the game to the given file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| SaveError::Format(e.to_string()))?;
+        fs::write(path, contents).map_err(SaveError::Io)
+    }
+
+    /// Create the game as it was when it was saved
+    /// The cards are created from the given card database
+    pub fn create_game(&self, database: &CardDatabase) -> Result<GameState, SaveError> {
+        let mut game_state = GameState::new(
+            self.player.create_player(database)?,
+            self.opponent.create_player(database)?,
+        );
+        game_state.turn = self.turn;
+        game_state.deck_validator = DeckValidator::new(self.format.clone());
+        game_state.starting_side = self.starting_side;
+        game_state.restore_history(self.history.clone());
+        Ok(game_state)
+    }
+}
+
+impl SavedPlayer {
+    fn new(player: &Player) -> SavedPlayer {
+        SavedPlayer {
+            name: player.name.clone(),
+            health: player.health,
+            resources: player.resources.clone(),
+            available_resources: player.available_resources.clone(),
+            original_deck: player
+                .original_deck
+                .iter()
+                .map(|card| card.id().to_string())
+                .collect(),
+            deck: player
+                .deck
+                .iter()
+                .map(|card| SavedCard::new(card.as_ref()))
+                .collect(),
+            hand: player.hand.iter().map(SavedInstance::new).collect(),
+            field: player.field.iter().map(SavedInstance::new).collect(),
+            graveyard: player.graveyard.iter().map(SavedInstance::new).collect(),
+            decked_out: player.decked_out,
+            next_card_id: player.next_card_id(),
+        }
+    }
+
+    fn create_player(&self, database: &CardDatabase) -> Result<Player, SaveError> {
+        let create_instances = |instances: &[SavedInstance]| {
+            instances
+                .iter()
+                .map(|instance| instance.create_instance(database))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let mut player = Player::new(&self.name);
+        player.health = self.health;
+        player.resources = self.resources.clone();
+        player.available_resources = self.available_resources.clone();
+        for id in &self.original_deck {
+            let card = database
+                .create_card(id)
+                .ok_or_else(|| SaveError::UnknownCard(id.clone()))?;
+            player.original_deck.push(card);
+        }
+        for card in &self.deck {
+            player.deck.push(card.create_card(database)?);
+        }
+        player.hand = create_instances(&self.hand)?;
+        player.field = create_instances(&self.field)?;
+        player.graveyard = create_instances(&self.graveyard)?;
+        player.decked_out = self.decked_out;
+        player.set_next_card_id(self.next_card_id);
+        Ok(player)
+    }
+}
+
+impl SavedCard {
+    fn new(card: &dyn Card) -> SavedCard {
+        SavedCard {
+            id: card.id().to_string(),
+            attack: card.attack().cloned(),
+            health: card.health().cloned(),
+        }
+    }
+
+    /// Create the card from the database, with the stats that it had when it was saved
+    fn create_card(&self, database: &CardDatabase) -> Result<Box<dyn Card>, SaveError> {
+        database
+            .create_card_with_stats(&self.id, self.attack, self.health)
+            .ok_or_else(|| SaveError::UnknownCard(self.id.clone()))
+    }
+}
+
+impl SavedInstance {
+    fn new(instance: &CardInstance) -> SavedInstance {
+        SavedInstance {
+            id: instance.id,
+            card: SavedCard::new(instance.card.as_ref()),
+            summoning_sick: instance.summoning_sick,
+            has_attacked: instance.has_attacked,
+        }
+    }
+
+    fn create_instance(&self, database: &CardDatabase) -> Result<CardInstance, SaveError> {
+        let mut instance = CardInstance::new(self.id, self.card.create_card(database)?);
+        instance.summoning_sick = self.summoning_sick;
+        instance.has_attacked = self.has_attacked;
+        Ok(instance)
+    }
+}
+
+/// The reasons why a game can not be saved or loaded
+#[derive(Debug)]
+pub enum SaveError {
+    /// The save file could not be read or written
+    Io(io::Error),
+    /// The save file is not a valid saved game
+    Format(String),
+    /// The game was saved with another version of the save format
+    WrongVersion(u32),
+    /// The game contains a card id that does not exist
+    UnknownCard(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "Could not access the save file: {}", e),
+            SaveError::Format(e) => write!(f, "Invalid save file: {}", e),
+            SaveError::WrongVersion(version) => write!(
+                f,
+                "Save has version {}, but only version {} is supported",
+                version, SAVE_VERSION
+            ),
+            SaveError::UnknownCard(id) => write!(f, "Unknown card {:?}", id),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{SaveError, SavedGame, SAVE_VERSION};
+    use crate::gamestate::Side;
+    use crate::test_utils::{database, new_game, play_actions, put_minion, skip_to_main};
+
+    #[test]
+    fn loaded_game_continues_like_the_saved_game() {
+        let mut game_state = new_game();
+        play_actions(&mut game_state, 60);
+        // The game should still be going on, with minions on the field
+        assert!(game_state.result().is_none());
+        assert!(!game_state.player.field.is_empty() || !game_state.opponent.field.is_empty());
+
+        let file = std::env::temp_dir().join(format!("save_test_{}.ron", std::process::id()));
+        SavedGame::new(&game_state).save(&file).unwrap();
+        let saved = SavedGame::load(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let mut loaded = saved.create_game(&database()).unwrap();
+        assert_eq!(loaded.state_hash(), game_state.state_hash());
+        assert_eq!(loaded.history(), game_state.history());
+        play_actions(&mut game_state, 100);
+        play_actions(&mut loaded, 100);
+        assert_eq!(loaded.state_hash(), game_state.state_hash());
+        let hand_ids = |game_state: &crate::gamestate::GameState| -> Vec<u32> {
+            game_state.player.hand.iter().map(|card| card.id).collect()
+        };
+        assert_eq!(hand_ids(&loaded), hand_ids(&game_state));
+    }
+
+    #[test]
+    fn save_with_another_version_is_not_loaded() {
+        let mut saved = SavedGame::new(&new_game());
+        saved.version = SAVE_VERSION + 1;
+        let file =
+            std::env::temp_dir().join(format!("save_version_test_{}.ron", std::process::id()));
+        saved.save(&file).unwrap();
+        let loaded = SavedGame::load(&file);
+        std::fs::remove_file(&file).unwrap();
+        assert!(
+            matches!(loaded, Err(SaveError::WrongVersion(version)) if version == SAVE_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn cards_keep_their_current_stats() {
+        let mut game_state = new_game();
+        skip_to_main(&mut game_state);
+        let minion = put_minion(&mut game_state, Side::Player, "generic_minion");
+        let instance = &mut game_state.player.field[minion];
+        *instance.card.health_mut().unwrap() = 2;
+        *instance.card.attack_mut().unwrap() = 7;
+        instance.has_attacked = true;
+
+        let loaded = SavedGame::new(&game_state)
+            .create_game(&database())
+            .unwrap();
+        let instance = &loaded.player.field[minion];
+        assert_eq!(instance.id, game_state.player.field[minion].id);
+        assert_eq!(instance.card.health(), Some(&2));
+        assert_eq!(instance.card.attack(), Some(&7));
+        assert!(instance.has_attacked);
+        assert!(!instance.summoning_sick);
+        assert_eq!(
+            loaded.player.next_card_id(),
+            game_state.player.next_card_id()
+        );
+    }
+
+    #[test]
+    fn save_with_an_unknown_card_is_not_loaded() {
+        let mut saved = SavedGame::new(&new_game());
+        saved.player.hand[0].card.id = "missing_card".to_string();
+        assert!(matches!(
+            saved.create_game(&database()),
+            Err(SaveError::UnknownCard(id)) if id == "missing_card"
+        ));
+    }
+}
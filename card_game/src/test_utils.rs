@@ -0,0 +1,63 @@
+//! Helpers that the tests of this crate share
+use crate::cards::CardDatabase;
+use crate::deck::Deck;
+use crate::gamestate::{Action, GameState, Phase, Player, Side};
+
+/// The directory with the cards and decks of the game
+pub const ASSETS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets");
+
+/// Load the cards of the game from `assets/cards.ron`
+pub fn database() -> CardDatabase {
+    CardDatabase::load(format!("{}/cards.ron", ASSETS)).unwrap()
+}
+
+/// Load the starter deck from `assets/decks/starter.deck`
+/// The starter deck starts with a light elemental, a buff card, a generic minion and a damage spell, in that order
+pub fn starter_deck() -> Deck {
+    Deck::load(format!("{}/decks/starter.deck", ASSETS)).unwrap()
+}
+
+/// Start a game where both players play with the starter deck
+pub fn new_game() -> GameState {
+    let database = database();
+    let deck = starter_deck();
+    let mut game_state = GameState::new(Player::new("Player"), Player::new("Opponent"));
+    game_state.player.original_deck = deck.create_cards(&database).unwrap();
+    game_state.opponent.original_deck = deck.create_cards(&database).unwrap();
+    game_state.start_game().unwrap();
+    game_state
+}
+
+/// Put a minion that is ready to attack on the field of the given side, and return its index on the field
+pub fn put_minion(game_state: &mut GameState, side: Side, id: &str) -> usize {
+    let card = database().create_card(id).unwrap();
+    let player = game_state.player_at_mut(side);
+    let mut instance = player.create_instance(card);
+    instance.summoning_sick = false;
+    player.field.push(instance);
+    player.field.len() - 1
+}
+
+/// Skip the start of the active side's turn, leaving the turn in the main phase
+pub fn skip_to_main(game_state: &mut GameState) {
+    game_state
+        .apply(game_state.turn.active, Action::AdvancePhase)
+        .unwrap();
+    assert_eq!(game_state.turn.phase, Phase::Main);
+}
+
+/// Play both sides by always taking the first legal action that isn't ending the turn, for the given amount of actions
+/// This plays every kind of action without needing an AI
+pub fn play_actions(game_state: &mut GameState, amount: usize) {
+    for _ in 0..amount {
+        let actions = game_state.legal_actions();
+        let action = match actions.iter().find(|action| **action != Action::EndTurn) {
+            Some(action) => *action,
+            None => match actions.first() {
+                Some(action) => *action,
+                None => return,
+            },
+        };
+        game_state.apply(game_state.turn.active, action).unwrap();
+    }
+}
@@ -2,7 +2,7 @@ pub trait VecUtils<T> {
     /// Try to remove a card at a given index
     /// Returns Some(T) if the index is valid
     /// Returns None if the index is not valid, either < 0 or >= len()
-    fn try_remove(&mut self, index: usize) -> Option<T>;
+    fn try_take(&mut self, index: usize) -> Option<T>;
 
     /// Try to insert a card
     /// If index >= 0 and index < len(), then it calls self.insert()
@@ -12,7 +12,7 @@ pub trait VecUtils<T> {
 }
 
 impl<T> VecUtils<T> for Vec<T> {
-    fn try_remove(&mut self, index: usize) -> Option<T> {
+    fn try_take(&mut self, index: usize) -> Option<T> {
         if self.len() <= index {
             None
         } else {
@@ -0,0 +1,179 @@
+use card_game::cards::CardDatabase;
+use card_game::deck::DeckValidator;
+use card_game::gamestate::{Action, Event, GameResult, GameState, Side};
+use card_game::network::{Connection, Message, NetworkError, PlayerInfo, Snapshot, Spectators};
+use std::thread;
+use std::time::Duration;
+
+/// How long a match waits before it checks for new messages again, if there were none
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A game between two players on the server
+/// The first player sits at `Side::Player` and takes the first turn, the second player sits at `Side::Opponent`
+/// Both players see themselves as `Side::Player`, so everything that the second player sends or receives is mirrored
+pub struct Match {
+    // Identifies this match in the log
+    id: u32,
+
+    game_state: GameState,
+
+    // The connections to both players, the player at `Side::Player` first
+    connections: [Connection; 2],
+
+    // Everyone that watches this match
+    spectators: Spectators,
+}
+
+impl Match {
+    /// Start a game between two players, with the given connections and decks
+    pub fn new(
+        id: u32,
+        database: &CardDatabase,
+        validator: &DeckValidator,
+        players: [(Connection, PlayerInfo); 2],
+        spectators: Spectators,
+    ) -> Result<Match, NetworkError> {
+        let [(mut first_connection, first), (mut second_connection, second)] = players;
+        let game_state = Match::create_game(database, validator, &first, &second);
+        let game_state = match game_state {
+            Ok(game_state) => game_state,
+            Err(e) => {
+                // Both players are told why they can't play before they're disconnected
+                let rejected = Message::Rejected(e.to_string());
+                let _ = first_connection.send(&rejected);
+                let _ = second_connection.send(&rejected);
+                return Err(e);
+            }
+        };
+        Ok(Match {
+            id,
+            game_state,
+            connections: [first_connection, second_connection],
+            spectators,
+        })
+    }
+
+    /// Create the game between both players, with the first player taking the first turn
+    fn create_game(
+        database: &CardDatabase,
+        validator: &DeckValidator,
+        first: &PlayerInfo,
+        second: &PlayerInfo,
+    ) -> Result<GameState, NetworkError> {
+        let mut game_state = GameState::new(
+            first.create_player(database)?,
+            second.create_player(database)?,
+        );
+        game_state.deck_validator = validator.clone();
+        game_state.start_game().map_err(NetworkError::InvalidGame)?;
+        Ok(game_state)
+    }
+
+    /// Send the game to the first player, who waited for an opponent
+    /// If they left while they were waiting, the match can't be played. The connection of the second player is then
+    /// given back, so they can wait for another opponent
+    pub fn start(mut self) -> Result<Match, (NetworkError, Connection)> {
+        // Sending to a player that left can still succeed, but the thread that receives their messages already knows
+        // that they are gone. A waiting player has nothing to send, so any message means they can't play
+        let sent = match self.connection(Side::Player).try_receive() {
+            Some(Err(e)) => Err(e),
+            Some(Ok(_)) => Err(NetworkError::UnexpectedMessage),
+            None => self.send_update(Side::Player, &[]),
+        };
+        match sent {
+            Ok(()) => Ok(self),
+            Err(e) => {
+                let [_, second_connection] = self.connections;
+                Err((e, second_connection))
+            }
+        }
+    }
+
+    /// Play the game until it's over, or until one of the players leaves
+    /// The second player gets the game at the start, the first player already got it in `start`. After that, both
+    /// players get a new snapshot after every action
+    /// Once the game is over, the match keeps running until the spectators saw the end of it
+    pub fn run(mut self) -> Result<GameResult, NetworkError> {
+        self.send_update(Side::Opponent, &[])?;
+        self.spectators.record(&[], &self.game_state);
+        let result = self.play();
+        while !self.spectators.is_done() {
+            self.spectators.update();
+            thread::sleep(POLL_INTERVAL);
+        }
+        result
+    }
+
+    /// Handle the messages of both players until the game is over
+    fn play(&mut self) -> Result<GameResult, NetworkError> {
+        loop {
+            self.spectators.update();
+            let mut received = false;
+            for side in &[Side::Player, Side::Opponent] {
+                let message = match self.connection(*side).try_receive() {
+                    Some(message) => message?,
+                    None => continue,
+                };
+                received = true;
+                match message {
+                    Message::Action(action) => self.handle_action(*side, action)?,
+                    _ => return Err(NetworkError::UnexpectedMessage),
+                }
+                // The players are allowed to leave as soon as the game is over
+                if let Some(result) = self.game_state.result() {
+                    return Ok(result);
+                }
+            }
+            if !received {
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+
+    /// Apply an action that the player at the given side sent, and send both players the result
+    /// If the action is not allowed, only the player that sent it gets the game again, so they can undo the action
+    fn handle_action(&mut self, side: Side, action: Action) -> Result<(), NetworkError> {
+        let action = match side {
+            Side::Player => action,
+            Side::Opponent => action.mirrored(),
+        };
+        match self.game_state.apply(side, action) {
+            Ok(events) => {
+                self.spectators.record(&events, &self.game_state);
+                self.send_update(Side::Player, &events)?;
+                self.send_update(Side::Opponent, &events)
+            }
+            Err(e) => {
+                println!(
+                    "Match {}: {} can not apply {:?}: {}",
+                    self.id,
+                    self.game_state.player_at(side).name,
+                    action,
+                    e
+                );
+                self.send_update(side, &[])
+            }
+        }
+    }
+
+    /// Send the given events and a snapshot of the game to the player at the given side
+    fn send_update(&mut self, side: Side, events: &[Event]) -> Result<(), NetworkError> {
+        let events = events
+            .iter()
+            .map(|event| match side {
+                Side::Player => *event,
+                Side::Opponent => event.mirrored(),
+            })
+            .collect();
+        let snapshot = Box::new(Snapshot::new(self.game_state.view(side)));
+        self.connection(side)
+            .send(&Message::Update { events, snapshot })
+    }
+
+    fn connection(&mut self, side: Side) -> &mut Connection {
+        match side {
+            Side::Player => &mut self.connections[0],
+            Side::Opponent => &mut self.connections[1],
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! A server that runs network games between players, without a window
+//! The server owns the games, so players can't see each other's hand or deck, and every action is checked against the
+//! rules before the other player gets to see it
+#![cfg_attr(not(debug_assertions), deny(dead_code))]
+
+mod game;
+
+use card_game::args::get_argument;
+use card_game::cards::CardDatabase;
+use card_game::deck::{DeckFormat, DeckValidator};
+use card_game::network::{
+    Connection, Message, NetworkError, PlayerInfo, Spectators, PROTOCOL_VERSION,
+};
+use game::Match;
+use std::collections::HashMap;
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{SendError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The file that all the cards of the game are loaded from
+const CARD_DATABASE_FILE: &str = "assets/cards.ron";
+
+/// The address that the server listens on when no other address is given
+const DEFAULT_ADDRESS: &str = "0.0.0.0:7777";
+
+/// A player that joined the server and is waiting for someone to play against
+struct WaitingPlayer {
+    connection: Connection,
+    info: PlayerInfo,
+}
+
+/// Everything that the threads of the server share
+struct Server {
+    database: CardDatabase,
+
+    // The rules that the decks of all players have to follow
+    validator: DeckValidator,
+
+    // The player that is waiting for an opponent, if any
+    // Players are matched in the order that they join
+    waiting: Mutex<Option<WaitingPlayer>>,
+
+    // The id of the next match, used to tell the matches apart in the log and to pick a match to watch
+    next_match_id: AtomicU32,
+
+    // The matches that are running right now, by id, with a way to add spectators to them
+    matches: Mutex<HashMap<u32, Sender<Connection>>>,
+
+    // How long spectators have to wait before they see what happened in a match
+    spectator_delay: Duration,
+}
+
+fn main() {
+    let database = match CardDatabase::load(CARD_DATABASE_FILE) {
+        Ok(database) => database,
+        Err(e) => {
+            println!("Could not load {}: {}", CARD_DATABASE_FILE, e);
+            return;
+        }
+    };
+
+    let args: Vec<String> = std::env::args().collect();
+    let address = match get_argument(&args, "--address") {
+        Ok(address) => address.unwrap_or(DEFAULT_ADDRESS),
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+    let format = match get_argument(&args, "--format") {
+        Ok(Some(file)) => match DeckFormat::load(file) {
+            Ok(format) => format,
+            Err(e) => {
+                println!("Could not load format {:?}: {}", file, e);
+                return;
+            }
+        },
+        Ok(None) => DeckFormat::default(),
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+    let spectator_delay = match get_argument(&args, "--spectator-delay") {
+        Ok(Some(seconds)) => match seconds.parse() {
+            Ok(seconds) => Duration::from_secs(seconds),
+            Err(_) => {
+                println!(
+                    "Invalid spectator delay {:?}, expected a number of seconds",
+                    seconds
+                );
+                return;
+            }
+        },
+        Ok(None) => Duration::from_secs(0),
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("Could not listen on {}: {}", address, e);
+            return;
+        }
+    };
+    println!("Listening on {}", address);
+
+    let server = Arc::new(Server {
+        database,
+        validator: DeckValidator::new(format),
+        waiting: Mutex::new(None),
+        next_match_id: AtomicU32::new(1),
+        matches: Mutex::new(HashMap::new()),
+        spectator_delay,
+    });
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                // Every player gets their own thread, which also runs the match once they have an opponent
+                let server = server.clone();
+                thread::spawn(move || server.handle_player(stream));
+            }
+            Err(e) => println!("Could not accept a player: {}", e),
+        }
+    }
+}
+
+impl Server {
+    /// Greet a player that just connected, and start a match once there is another player to play against
+    fn handle_player(&self, stream: TcpStream) {
+        let address = stream
+            .peer_addr()
+            .map(|address| address.to_string())
+            .unwrap_or_else(|_| "unknown address".to_string());
+        let mut player = match self.greet(stream) {
+            Ok(Some(player)) => player,
+            Ok(None) => {
+                println!("Spectator joined from {}", address);
+                return;
+            }
+            Err(e) => {
+                println!("Player from {} can not play: {}", address, e);
+                return;
+            }
+        };
+        println!("{} joined from {}", player.info.name, address);
+
+        loop {
+            // The lock is only held to pick an opponent, the match itself runs without it
+            let opponent = {
+                let mut waiting = self.waiting.lock().unwrap();
+                match waiting.take() {
+                    Some(opponent) => opponent,
+                    None => {
+                        *waiting = Some(player);
+                        return;
+                    }
+                }
+            };
+
+            let id = self.next_match_id.fetch_add(1, Ordering::SeqCst);
+            let opponent_name = opponent.info.name.clone();
+            let info = player.info.clone();
+            let players = [
+                (opponent.connection, opponent.info),
+                (player.connection, player.info),
+            ];
+            let (spectators, joining) = Spectators::new(self.spectator_delay);
+            let started = match Match::new(id, &self.database, &self.validator, players, spectators)
+            {
+                Ok(started) => started.start(),
+                Err(e) => {
+                    println!("Match {} stopped: {}", id, e);
+                    return;
+                }
+            };
+            let started = match started {
+                Ok(started) => started,
+                // The opponent left while they were waiting, so the player waits for someone else instead
+                Err((e, connection)) => {
+                    println!("Match {} can not start: {}", id, e);
+                    player = WaitingPlayer { connection, info };
+                    continue;
+                }
+            };
+
+            println!("Match {}: {} against {}", id, opponent_name, info.name);
+            self.matches.lock().unwrap().insert(id, joining);
+            match started.run() {
+                Ok(result) => println!("Match {}: {:?}", id, result),
+                Err(e) => println!("Match {} stopped: {}", id, e),
+            }
+            self.matches.lock().unwrap().remove(&id);
+            return;
+        }
+    }
+
+    /// Wait for the `Message::Hello` of a player, and check that they can play on this server
+    /// If they can't, they get a `Message::Rejected` with the reason
+    /// Returns None if they sent a `Message::Spectate` instead, in which case they now watch a match
+    fn greet(&self, stream: TcpStream) -> Result<Option<WaitingPlayer>, NetworkError> {
+        let mut connection = Connection::new(stream)?;
+        let info = match connection.receive()? {
+            Message::Hello(info) => info,
+            Message::Spectate { version, game } => {
+                return self.spectate(connection, version, game).map(|_| None)
+            }
+            _ => return Err(NetworkError::UnexpectedMessage),
+        };
+        let checked = info
+            .check_version()
+            .and_then(|_| info.check_deck(&self.database, &self.validator));
+        if let Err(e) = checked {
+            connection.send(&Message::Rejected(e.to_string()))?;
+            return Err(e);
+        }
+        Ok(Some(WaitingPlayer { connection, info }))
+    }
+
+    /// Let a spectator watch the match with the given id, or the newest match if no id is given
+    fn spectate(
+        &self,
+        mut connection: Connection,
+        version: u32,
+        game: Option<u32>,
+    ) -> Result<(), NetworkError> {
+        if version != PROTOCOL_VERSION {
+            let error = NetworkError::WrongVersion(version);
+            connection.send(&Message::Rejected(error.to_string()))?;
+            return Err(error);
+        }
+        let matches = self.matches.lock().unwrap();
+        let joining = match game {
+            Some(id) => matches.get(&id),
+            None => matches.keys().max().and_then(|id| matches.get(id)),
+        };
+        let mut connection = match joining {
+            Some(joining) => match joining.send(connection) {
+                Ok(()) => return Ok(()),
+                // The match ended just now, so there is nothing left to watch
+                Err(SendError(connection)) => connection,
+            },
+            None => connection,
+        };
+        let reason = match game {
+            Some(id) => format!("There is no match {} on this server", id),
+            None => "There are no matches on this server".to_string(),
+        };
+        connection.send(&Message::Rejected(reason.clone()))?;
+        Err(NetworkError::Rejected(reason))
+    }
+}
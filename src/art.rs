@@ -0,0 +1,45 @@
+use glium::texture::{RawImage2d, Texture2d};
+use glium::Display;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The directory that the art of the cards is loaded from. See `CardDefinition::art`
+const ART_DIRECTORY: &str = "assets";
+
+/// Holds the textures of all the card art that was loaded so far
+/// Every image is only loaded once, even if it's used by many cards
+#[derive(Default)]
+pub struct ArtCache {
+    // The loaded textures, keyed by the path in the card definition
+    // This is None if the image could not be loaded, so we don't try to load it again every time
+    textures: HashMap<String, Option<Texture2d>>,
+}
+
+impl ArtCache {
+    /// Get the texture of the art at the given path, relative to the assets directory
+    /// The image is loaded the first time that it's needed
+    /// Returns None if the image does not exist or can not be read, in which case the card should show a placeholder
+    pub fn get(&mut self, display: &Display, path: &str) -> Option<&Texture2d> {
+        self.textures
+            .entry(path.to_string())
+            .or_insert_with(|| match ArtCache::load(display, path) {
+                Ok(texture) => Some(texture),
+                Err(e) => {
+                    println!("Could not load card art {:?}: {}", path, e);
+                    None
+                }
+            })
+            .as_ref()
+    }
+
+    /// Load an image from the assets directory and upload it as a texture
+    fn load(display: &Display, path: &str) -> Result<Texture2d, String> {
+        let image = image::open(Path::new(ART_DIRECTORY).join(path))
+            .map_err(|e| e.to_string())?
+            .to_rgba8();
+        let dimensions = image.dimensions();
+        // Images are stored from the top row down, but OpenGL textures start at the bottom row
+        let raw = RawImage2d::from_raw_rgba_reversed(&image.into_raw(), dimensions);
+        Texture2d::new(display, raw).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,470 @@
+mod positioning;
+
+pub use self::positioning::{AreaReference, CardReference};
+
+use crate::card_wrapper::CardWrapper;
+use crate::constants::{CARD_HEIGHT, CARD_IN_HAND_SPACING, CARD_ON_FIELD_SPACING};
+use crate::point::Point;
+use card_game::cards::{CardPlayEffect, HiddenCard};
+use card_game::gamestate::{Action, GameState, Phase, PlayerView, Side, SideView, Target};
+use card_game::CardInstance;
+
+/// The cards of the game as they're shown on the screen
+/// This holds a CardWrapper for every card in the hands and on the fields of the `GameState`, and turns the
+/// interactions that the player has with these cards into moves on the `GameState`
+#[derive(Default)]
+pub struct Board {
+    // The cards in the players hand
+    pub hand: Vec<CardWrapper>,
+
+    // The cards on the players side of the field
+    pub field: Vec<CardWrapper>,
+
+    // The cards in the opponents hand
+    pub opponent_hand: Vec<CardWrapper>,
+
+    // The cards on the opponents side of the field
+    pub opponent_field: Vec<CardWrapper>,
+
+    // If the player is dragging a card, we hold a reference here
+    pub dragging_card: Option<CardReference>,
+
+    // If the player picked a spell that needs a target, we hold the index of that card in the hand here
+    pub targeting_card: Option<usize>,
+}
+
+impl Board {
+    /// Update the cards on the board to match the game as the player sees it, and position them on the screen
+    /// Cards that were already on the board keep their position and texture, even if they moved to another area,
+    /// so a card that is played from the hand slides to the field instead of popping up
+    pub fn sync(&mut self, view: PlayerView, screen_size: &Point) {
+        let mut existing = Vec::new();
+        existing.extend(self.hand.drain(..).map(|card| (Side::Player, card)));
+        existing.extend(self.field.drain(..).map(|card| (Side::Player, card)));
+        existing.extend(
+            self.opponent_hand
+                .drain(..)
+                .map(|card| (Side::Opponent, card)),
+        );
+        existing.extend(
+            self.opponent_field
+                .drain(..)
+                .map(|card| (Side::Opponent, card)),
+        );
+
+        let player = view.player(Side::Player);
+        let opponent = view.player(Side::Opponent);
+        self.hand = Board::wrap_cards(&Board::hand_cards(&player), Side::Player, &mut existing);
+        self.field = Board::wrap_cards(player.field(), Side::Player, &mut existing);
+        self.opponent_hand =
+            Board::wrap_cards(&Board::hand_cards(&opponent), Side::Opponent, &mut existing);
+        self.opponent_field = Board::wrap_cards(opponent.field(), Side::Opponent, &mut existing);
+
+        // Only the backs of the cards in a hidden hand can be seen, e.g. the opponent's hand or both hands of a
+        // spectator
+        let player_hand_hidden = Board::is_hand_hidden(&player);
+        for cardwrapper in &mut self.hand {
+            cardwrapper.set_face_down(player_hand_hidden);
+        }
+        let opponent_hand_hidden = Board::is_hand_hidden(&opponent);
+        for cardwrapper in &mut self.opponent_hand {
+            cardwrapper.set_face_down(opponent_hand_hidden);
+        }
+        for cardwrapper in &mut self.opponent_field {
+            cardwrapper.set_face_down(false);
+        }
+
+        self.update_card_origins(screen_size);
+    }
+
+    /// Get the cards in the hand of a side, with a `HiddenCard` in place of every card that the player can't see
+    fn hand_cards(player: &SideView) -> Vec<CardInstance> {
+        match player.hand() {
+            Some(hand) => hand.to_vec(),
+            None => player
+                .hand_ids()
+                .map(|id| CardInstance::new(id, Box::new(HiddenCard)))
+                .collect(),
+        }
+    }
+
+    /// Returns true if the viewer can't see the cards in the hand of a side
+    /// A game that was created from a `Snapshot` holds `HiddenCard`s in the hands that the viewer can't see
+    fn is_hand_hidden(player: &SideView) -> bool {
+        match player.hand() {
+            Some(hand) => hand
+                .iter()
+                .any(|instance| instance.card.id() == HiddenCard::ID),
+            None => true,
+        }
+    }
+
+    /// Create a CardWrapper for every CardInstance in the list
+    /// If there is already a CardWrapper for an instance in `existing`, that wrapper is reused
+    fn wrap_cards(
+        instances: &[CardInstance],
+        side: Side,
+        existing: &mut Vec<(Side, CardWrapper)>,
+    ) -> Vec<CardWrapper> {
+        instances
+            .iter()
+            .map(|instance| {
+                let index = existing
+                    .iter()
+                    .position(|(s, wrapper)| *s == side && wrapper.id == instance.id);
+                match index {
+                    Some(index) => {
+                        let (_, mut wrapper) = existing.swap_remove(index);
+                        wrapper.set_card(instance.card.clone_box());
+                        wrapper
+                    }
+                    None => CardWrapper::new(instance.id, instance.card.clone_box()),
+                }
+            })
+            .collect()
+    }
+
+    /// Apply an action to the game state, and update the board to show the result
+    /// If the action is not allowed, the reason is printed and the board is only reset to the current game state
+    /// Returns true if the action was applied
+    pub fn apply(
+        &mut self,
+        game_state: &mut GameState,
+        action: Action,
+        screen_size: &Point,
+    ) -> bool {
+        let applied = match game_state.apply(Side::Player, action) {
+            Ok(_) => true,
+            Err(e) => {
+                println!("Could not apply {:?}: {}", action, e);
+                false
+            }
+        };
+        self.sync(game_state.view(Side::Player), screen_size);
+        applied
+    }
+
+    /// Stop dragging or targetting with any card
+    /// This should be called when the game state changes outside of the board, e.g. when a turn ends
+    pub fn reset_interaction(&mut self) {
+        if let Some(reference) = self.dragging_card.take() {
+            if let Some(cardwrapper) = self.get_card_mut(&reference) {
+                cardwrapper.dragging = false;
+            }
+        }
+        self.targeting_card = None;
+    }
+
+    /// Returns true if it's the turn of the player behind this screen, and they're allowed to play cards or attack
+    fn player_can_act(view: PlayerView) -> bool {
+        let turn = view.turn();
+        view.result().is_none()
+            && turn.active == Side::Player
+            && (turn.phase == Phase::Main || turn.phase == Phase::Combat)
+    }
+
+    /// Update the positions of the cards in the given `list`
+    /// They will be positioned in the center of the screen, at y coordinate `position_y`
+    /// There will be `spacing` amount of pixels between the middle of the cards, not between the sides
+    /// Finally, `screen_size` needs to be passed to calculate the position correctly
+    fn update_positions_of_list(
+        list: &mut [CardWrapper],
+        position_y: f32,
+        spacing: f32,
+        screen_size: &Point,
+    ) {
+        // calculate the position of the left-most card
+        let mut position = Point::new(
+            (screen_size.x / 2f32) - ((list.len() as f32 * spacing) - spacing) / 2f32,
+            position_y,
+        );
+
+        for card in list.iter_mut() {
+            card.set_position(position);
+            position.x += spacing; // the next card is a simple `spacing` away
+        }
+    }
+
+    /// Get all the cards on the board, in the order that they should be drawn
+    /// The opponent's cards come first, so the player's cards are drawn on top of them
+    pub fn cards_mut(&mut self) -> impl Iterator<Item = &mut CardWrapper> {
+        self.opponent_hand
+            .iter_mut()
+            .chain(self.opponent_field.iter_mut())
+            .chain(self.field.iter_mut())
+            .chain(self.hand.iter_mut())
+    }
+
+    /// Get a mutable reference to a card based on the given CardReference
+    /// This will be None if the given `reference.index` is out of range of the list
+    pub fn get_card_mut(&mut self, reference: &CardReference) -> Option<&mut CardWrapper> {
+        match reference.area {
+            AreaReference::PlayerHand => self.hand.get_mut(reference.index),
+            AreaReference::PlayerField => self.field.get_mut(reference.index),
+            AreaReference::OpponentHand => self.opponent_hand.get_mut(reference.index),
+            AreaReference::OpponentField => self.opponent_field.get_mut(reference.index),
+        }
+    }
+
+    /// Update the position of all cards. This should be called after a screen resize or a card position change so all cards are
+    /// rendered at the right position.
+    /// The opponent's side is a mirror of the player's side, with their hand at the top of the screen and their field
+    /// right above the middle of the screen
+    pub fn update_card_origins(&mut self, screen_size: &Point) {
+        Board::update_positions_of_list(
+            &mut self.opponent_hand,
+            CARD_HEIGHT / 2f32,
+            CARD_IN_HAND_SPACING,
+            screen_size,
+        );
+        Board::update_positions_of_list(
+            &mut self.opponent_field,
+            (screen_size.y - CARD_HEIGHT) / 2f32,
+            CARD_ON_FIELD_SPACING,
+            screen_size,
+        );
+        Board::update_positions_of_list(
+            &mut self.hand,
+            screen_size.y - CARD_HEIGHT / 2f32,
+            CARD_IN_HAND_SPACING,
+            screen_size,
+        );
+        Board::update_positions_of_list(
+            &mut self.field,
+            (screen_size.y + CARD_HEIGHT) / 2f32,
+            CARD_ON_FIELD_SPACING,
+            screen_size,
+        );
+
+        // The spell that the player is picking a target for sticks out of the hand
+        if let Some(index) = self.targeting_card {
+            if let Some(cardwrapper) = self.hand.get_mut(index) {
+                let position = *cardwrapper.position() - Point::new(0.0, CARD_HEIGHT / 4f32);
+                cardwrapper.set_position(position);
+            }
+        }
+    }
+
+    /// Move the mouse to a given position.
+    /// If `self.dragging_card` is not None, this will move the selected card with the mouse position
+    pub fn mouse_moved_to(&mut self, mouse_position: &Point) {
+        if let Some(reference) = self.dragging_card {
+            if let Some(ref mut cardwrapper) = self.get_card_mut(&reference) {
+                cardwrapper.mouse_moved(mouse_position);
+            }
+        }
+    }
+
+    /// Get the result of a card drag event based on the area that the card came from, and
+    /// what the card is allowed to do based on the result of `wrapper.card.play_effects()`
+    ///
+    /// Returns the first valid combination, or None if no valid combination is found
+    fn get_card_drag_result(wrapper: &CardWrapper, area: &AreaReference) -> Option<CardPlayEffect> {
+        for effect in wrapper.card.play_effects() {
+            match (effect, area) {
+                (CardPlayEffect::SummonMinion, &AreaReference::PlayerHand) => {
+                    return Some(CardPlayEffect::SummonMinion)
+                }
+                (CardPlayEffect::SummonMinion, _) => {}
+                (x, _) => return Some(x),
+            }
+        }
+        None
+    }
+
+    /// Detects if the mouse is currently over a card
+    /// If it is, and the card can be dragged, it'll make this card drag and
+    /// follow the mouse position on subsequential `mouse_moved_to` calls
+    /// If the player is picking a target for a spell, this will cast the spell on the target under the mouse
+    pub fn mouse_pressed_at(
+        &mut self,
+        game_state: &mut GameState,
+        mouse_position: &Point,
+        screen_size: &Point,
+    ) {
+        if !Board::player_can_act(game_state.view(Side::Player)) {
+            return;
+        }
+        if let Some(index) = self.targeting_card {
+            if let Some(target) = self.get_target_from_point(mouse_position, screen_size) {
+                let action = Action::CastSpell {
+                    card: index,
+                    target,
+                };
+                if self.apply(game_state, action, screen_size) {
+                    self.targeting_card = None;
+                    self.update_card_origins(screen_size);
+                }
+            }
+            return;
+        }
+        let in_main_phase = game_state.turn.phase == Phase::Main;
+
+        // Make a list of card lists and the position the card is at
+        let mut lists = [
+            (&mut self.hand.iter_mut(), AreaReference::PlayerHand),
+            (&mut self.field.iter_mut(), AreaReference::PlayerField),
+        ];
+        for &mut (ref mut list, area) in &mut lists {
+            let length = list.len();
+            // The right-most card should be the one on top, so we need to iterate through the list reversely
+            for (index, ref mut card) in list.rev().enumerate() {
+                if card.contains(mouse_position) {
+                    let position = CardReference {
+                        area,
+                        index: length - index - 1, // because we're iterating from the end, we need to correct the card index
+                    };
+                    if area == AreaReference::PlayerField {
+                        // minions on the field can be dragged onto an opponent's minion or portrait to attack
+                        let can_attack = game_state
+                            .view(Side::Player)
+                            .player(Side::Player)
+                            .field()
+                            .get(position.index)
+                            .is_some_and(|instance| instance.can_attack());
+                        if can_attack {
+                            card.drag_start(mouse_position);
+                            self.dragging_card = Some(position);
+                            return;
+                        }
+                        continue;
+                    }
+                    if !in_main_phase {
+                        continue;
+                    }
+                    match Board::get_card_drag_result(card, &area) {
+                        None => {}
+                        Some(CardPlayEffect::SummonMinion) => {
+                            // if we can play this card, make it draggable and return
+                            card.drag_start(mouse_position);
+                            self.dragging_card = Some(position);
+                            return;
+                        }
+                        Some(CardPlayEffect::Target(_)) => {
+                            // if we can target something, wait for the player to click on a target
+                            self.targeting_card = Some(position.index);
+                            self.update_card_origins(screen_size);
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Based on the given list of CardWrappers
+    /// Get the index that the mouse is hovering at, based on the mouse's x position
+    fn get_card_index(cards: &[CardWrapper], mouse_x: f32) -> usize {
+        for (index, card) in cards.iter().enumerate() {
+            if card.drag_position().x > mouse_x {
+                return index;
+            }
+        }
+        cards.len()
+    }
+
+    /// Get the play area and the card index of the point on the screen
+    /// This returns a CardReference with the area, and the index that a card should be placed at
+    /// From top to bottom, each quarter of the screen is the opponent's hand, the opponent's field, the player's field
+    /// and the player's hand
+    /// Returns None if the point is outside of the screen
+    fn get_area_from_point(&self, point: &Point, screen_size: &Point) -> Option<CardReference> {
+        let y_factor = point.y / screen_size.y;
+        let (area, cards) = if !(0.0..=1.0).contains(&y_factor) {
+            return None;
+        } else if y_factor < 0.25 {
+            (AreaReference::OpponentHand, &self.opponent_hand)
+        } else if y_factor < 0.5 {
+            (AreaReference::OpponentField, &self.opponent_field)
+        } else if y_factor < 0.75 {
+            (AreaReference::PlayerField, &self.field)
+        } else {
+            (AreaReference::PlayerHand, &self.hand)
+        };
+        Some(CardReference {
+            area,
+            index: Board::get_card_index(cards, point.x),
+        })
+    }
+
+    /// Stop picking a target for a spell, the spell goes back into the hand
+    pub fn cancel_targeting(&mut self, screen_size: &Point) {
+        self.targeting_card = None;
+        self.update_card_origins(screen_size);
+    }
+
+    /// Get the target that is at the given point on the screen
+    /// This is either a minion on one of the fields, or one of the portraits
+    /// The opponent's portrait is at the top quarter of the screen, the player's portrait at the bottom quarter next to their hand
+    /// Returns None if there is nothing to target at that point
+    fn get_target_from_point(&self, point: &Point, screen_size: &Point) -> Option<Target> {
+        let find_minion = |side: Side, field: &[CardWrapper]| {
+            field
+                .iter()
+                .rposition(|cardwrapper| cardwrapper.contains(point))
+                .map(|index| Target::Minion(side, index))
+        };
+        let y_factor = point.y / screen_size.y;
+        if let Some(target) = find_minion(Side::Opponent, &self.opponent_field)
+            .or_else(|| find_minion(Side::Player, &self.field))
+        {
+            Some(target)
+        } else if y_factor < 0.25 {
+            Some(Target::Hero(Side::Opponent))
+        } else if y_factor > 0.75
+            && !self
+                .hand
+                .iter()
+                .any(|cardwrapper| cardwrapper.contains(point))
+        {
+            // the player's hand overlaps with their portrait, so clicking a card in hand should not target the hero
+            Some(Target::Hero(Side::Player))
+        } else {
+            None
+        }
+    }
+
+    /// Trigger a mouse release event
+    /// If we're dragging a card, this will attempt to play that card from hand, or attack with it
+    /// Because we need to call `update_card_origins` and `get_area_from_point`, we also need the `screen_size` argument
+    pub fn mouse_released(&mut self, game_state: &mut GameState, screen_size: &Point) {
+        let mut position = None;
+        if let Some(reference) = self.dragging_card.take() {
+            if let Some(ref mut cardwrapper) = self.get_card_mut(&reference) {
+                cardwrapper.dragging = false;
+                position = Some(*cardwrapper.drag_position());
+            }
+            if reference.area == AreaReference::PlayerField {
+                // a minion on the field was dragged, see if it was dropped on something to attack
+                let target = position
+                    .and_then(|p| self.get_target_from_point(&p, screen_size))
+                    .filter(|target| target.side() == Side::Opponent);
+                if let Some(target) = target {
+                    let action = Action::Attack {
+                        attacker: reference.index,
+                        target,
+                    };
+                    self.apply(game_state, action, screen_size);
+                }
+                return;
+            }
+            // TODO: Combine these two if statements?
+            // Maybe even join it with the match statement?
+            if let Some(position) = position {
+                if let Some(position) = self.get_area_from_point(&position, screen_size) {
+                    match (reference.area, position.area) {
+                        (AreaReference::PlayerHand, AreaReference::PlayerField) => {
+                            // if we're playing a card from hand and to the field, play it and update the card positions
+                            let action = Action::PlayCard {
+                                from: reference.index,
+                                to: position.index,
+                            };
+                            self.apply(game_state, action, screen_size);
+                        }
+                        x => println!("Unknown action combination: {:?}", x),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,6 @@
 /// Holds a reference to the different areas on the board that cardwrappers can be positioned at
-/// This corresponds to a specific list of the current game state
-#[derive(Debug, Clone, Copy)]
-#[allow(dead_code)]
+/// This corresponds to a specific list of cards on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AreaReference {
     PlayerHand,
     PlayerField,
@@ -10,7 +9,7 @@ pub enum AreaReference {
 }
 
 /// Holds a reference to a single point on the board that cardwrappers can be positioned at
-/// This is a combined value of AreaReference and an index, which corresponds to a specific list and index in that list of the current game state
+/// This is a combined value of AreaReference and an index, which corresponds to a specific list and index in that list of the board
 #[derive(Debug, Clone, Copy)]
 pub struct CardReference {
     pub area: AreaReference,
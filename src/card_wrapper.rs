@@ -1,16 +1,20 @@
-use crate::cards::Card;
 use crate::constants::{BOUNCE_BACK_FACTOR, CARD_HEIGHT, CARD_WIDTH};
 use crate::point::Point;
 use crate::render_state::RenderState;
+use card_game::cards::{Card, ResourceType};
 use glium::framebuffer::SimpleFrameBuffer;
 use glium::texture::Texture2d;
 use glium::uniform;
-use glium::{Blend, DrawParameters, Rect, Surface};
-use glium_text::{self, TextDisplay};
+use glium::uniforms::MagnifySamplerFilter;
+use glium::{Blend, BlitTarget, DrawParameters, Rect, Surface};
+use glium_text::{self, FontTexture, TextDisplay};
 
 /// Holds a card at a specific position on the screen
 /// Also contains the card's texture
 pub struct CardWrapper {
+    /// The id of the `CardInstance` in the game state that this cardwrapper is showing
+    pub id: u32,
+
     /// The position that the card is currently at
     current_position: Point,
 
@@ -29,14 +33,19 @@ pub struct CardWrapper {
     /// If this is None, it will be generated on the next frame
     pub texture: Option<Texture2d>,
 
-    /// A reference to the card that this cardwrapper is holding
+    /// A copy of the card that this cardwrapper is showing
+    /// This gets replaced every time the board is synced with the game state
     pub card: Box<dyn Card>,
+
+    /// Is the back of the card shown instead of the card itself? This is used for the cards in the opponent's hand
+    face_down: bool,
 }
 
 impl CardWrapper {
     /// Create a new card wrapper at 0/0 for the given card
-    pub fn new(card: Box<dyn Card>) -> CardWrapper {
+    pub fn new(id: u32, card: Box<dyn Card>) -> CardWrapper {
         CardWrapper {
+            id,
             position: Point::zero(),
             current_position: Point::zero(),
 
@@ -44,6 +53,28 @@ impl CardWrapper {
             drag_offset: Point::zero(),
             texture: None,
             card,
+            face_down: false,
+        }
+    }
+
+    /// Replace the card that this cardwrapper is showing with a newer copy of the same card
+    /// The texture is generated again if the card looks different, e.g. because it was damaged or buffed
+    pub fn set_card(&mut self, card: Box<dyn Card>) {
+        let changed = card.attack() != self.card.attack()
+            || card.health() != self.card.health()
+            || card.cost() != self.card.cost();
+        self.card = card;
+        if changed {
+            self.texture = None;
+        }
+    }
+
+    /// Show the back or the front of the card
+    /// The texture is generated again if this changes
+    pub fn set_face_down(&mut self, face_down: bool) {
+        if self.face_down != face_down {
+            self.face_down = face_down;
+            self.texture = None;
         }
     }
 
@@ -55,6 +86,10 @@ impl CardWrapper {
     pub fn drag_position(&self) -> &Point {
         &self.current_position
     }
+    /// Gets the snap-back position of the card
+    pub fn position(&self) -> &Point {
+        &self.position
+    }
     /// Set the snap-back position of the card
     pub fn set_position(&mut self, p: Point) {
         self.position = p;
@@ -97,6 +132,8 @@ impl CardWrapper {
     }
 
     /// Generate the cards texture based on the given card
+    /// From top to bottom, a card shows its cost, its name, its art, whether it's a minion or a spell, its description
+    /// and, for minions, its attack in the bottom left corner and its health in the bottom right corner
     fn generate_texture(&self, render_state: &mut RenderState) -> Texture2d {
         let texture: Texture2d =
             Texture2d::empty(render_state.window, CARD_WIDTH as u32, CARD_HEIGHT as u32).unwrap();
@@ -104,61 +141,133 @@ impl CardWrapper {
             let mut frame_buffer: SimpleFrameBuffer =
                 SimpleFrameBuffer::new(render_state.window, &texture).unwrap();
             frame_buffer.clear_color(0.0, 0.0, 0.0, 1.0);
-            frame_buffer.clear(
-                Some(&Rect {
-                    left: 1,
-                    bottom: 1,
-                    width: CARD_WIDTH as u32 - 2,
-                    height: CARD_HEIGHT as u32 - 2,
-                }),
-                Some((1.0, 1.0, 1.0, 1.0)),
-                false,
-                None,
-                None,
+            if self.face_down {
+                // The back of a card is a plain dark red, so the opponent's cards can't be seen
+                fill_rect(
+                    &mut frame_buffer,
+                    5,
+                    5,
+                    CARD_WIDTH as u32 - 10,
+                    CARD_HEIGHT as u32 - 10,
+                    (0.5, 0.1, 0.1, 1.0),
+                );
+                return texture;
+            }
+            fill_rect(
+                &mut frame_buffer,
+                1,
+                1,
+                CARD_WIDTH as u32 - 2,
+                CARD_HEIGHT as u32 - 2,
+                WHITE,
             );
 
-            // TODO: Properly calculate the positions and size of the font
-            let text = TextDisplay::new(
-                render_state.text_system,
-                render_state.font,
+            // The cost is shown as a row of gems in the top right corner, one for every resource type
+            let mut left = CARD_WIDTH as u32 - GEM_MARGIN;
+            for (resource, amount) in self.card.cost() {
+                left = left.saturating_sub(GEM_SIZE + GEM_MARGIN);
+                let (color, text_color) = resource_colors(resource);
+                draw_number_box(
+                    &mut frame_buffer,
+                    render_state,
+                    (left, CARD_HEIGHT as u32 - GEM_MARGIN - GEM_SIZE),
+                    GEM_SIZE,
+                    amount,
+                    color,
+                    text_color,
+                );
+            }
+
+            draw_text(
+                &mut frame_buffer,
+                render_state,
                 self.card.name(),
+                Point::new(-0.95, 0.5),
+                NAME_HEIGHT,
+                BLACK,
             );
-            let matrix = [
-                [0.1, 0.0, 0.0, 0.0],
-                [0.0, 0.075, 0.0, 0.0],
-                [0.0, 0.0, 0.1, 0.0],
-                [-0.95, 0.9, 0.0, 1.0],
-            ];
-            glium_text::draw(
-                &text,
-                render_state.text_system,
+            self.draw_art(&mut frame_buffer, render_state);
+            let type_line = if self.card.health().is_some() {
+                "Minion"
+            } else {
+                "Spell"
+            };
+            draw_text(
                 &mut frame_buffer,
-                matrix,
-                (0.0, 0.0, 0.0, 1.0),
+                render_state,
+                type_line,
+                Point::new(-0.95, -0.3),
+                TEXT_HEIGHT,
+                GREY,
             );
-            let mut y = 0.7;
+            let mut y = -0.42;
             for line in self.card.description().lines() {
-                let text = TextDisplay::new(render_state.text_system, render_state.font, line);
-                let matrix = [
-                    [0.1, 0.0, 0.0, 0.0],
-                    [0.0, 0.075, 0.0, 0.0],
-                    [0.0, 0.0, 0.1, 0.0],
-                    [-0.95, y, 0.0, 1.0],
-                ];
-                glium_text::draw(
-                    &text,
-                    render_state.text_system,
+                draw_text(
                     &mut frame_buffer,
-                    matrix,
-                    (0.0, 0.0, 0.0, 1.0),
+                    render_state,
+                    line,
+                    Point::new(-0.95, y),
+                    TEXT_HEIGHT,
+                    BLACK,
                 );
                 y -= 0.1;
             }
+
+            if let (Some(attack), Some(health)) = (self.card.attack(), self.card.health()) {
+                draw_number_box(
+                    &mut frame_buffer,
+                    render_state,
+                    (STAT_MARGIN, STAT_MARGIN),
+                    STAT_SIZE,
+                    *attack,
+                    (0.9, 0.7, 0.1, 1.0),
+                    BLACK,
+                );
+                draw_number_box(
+                    &mut frame_buffer,
+                    render_state,
+                    (CARD_WIDTH as u32 - STAT_MARGIN - STAT_SIZE, STAT_MARGIN),
+                    STAT_SIZE,
+                    *health,
+                    (0.8, 0.1, 0.1, 1.0),
+                    WHITE,
+                );
+            }
         }
 
         texture
     }
 
+    /// Draw the art of the card in the frame of the card texture
+    /// If the card has no art, or the art can't be loaded, a grey placeholder is drawn instead
+    fn draw_art(&self, frame_buffer: &mut SimpleFrameBuffer, render_state: &mut RenderState) {
+        let target = BlitTarget {
+            left: ART_LEFT,
+            bottom: ART_BOTTOM,
+            width: CARD_WIDTH as i32 - ART_LEFT as i32 * 2,
+            height: ART_HEIGHT as i32,
+        };
+        let art = match self.card.art() {
+            Some(path) => render_state.art.get(render_state.window, path),
+            None => None,
+        };
+        match art {
+            Some(texture) => texture.as_surface().blit_whole_color_to(
+                frame_buffer,
+                &target,
+                MagnifySamplerFilter::Linear,
+            ),
+            None => fill_rect(
+                frame_buffer,
+                target.left,
+                target.bottom,
+                target.width as u32,
+                ART_HEIGHT,
+                GREY,
+            ),
+        }
+    }
+
     /// Draw a card to the screen
     pub fn draw(&mut self, render_state: &mut RenderState) {
         // if we have no texture, generate it
@@ -189,3 +298,149 @@ impl CardWrapper {
         }
     }
 }
+
+/// A color as red, green, blue and alpha values between 0 and 1
+type Color = (f32, f32, f32, f32);
+
+/// The color of the background of a card
+const WHITE: Color = (1.0, 1.0, 1.0, 1.0);
+
+/// The color of the text on a card
+const BLACK: Color = (0.0, 0.0, 0.0, 1.0);
+
+/// The color of the type line of a card
+const GREY: Color = (0.4, 0.4, 0.4, 1.0);
+
+/// The width and height of a cost gem, in pixels
+const GEM_SIZE: u32 = 24;
+
+/// The space around a cost gem, in pixels
+const GEM_MARGIN: u32 = 4;
+
+/// The width and height of the attack and health boxes, in pixels
+const STAT_SIZE: u32 = 30;
+
+/// The distance between the attack and health boxes and the sides of the card, in pixels
+const STAT_MARGIN: u32 = 4;
+
+/// The distance between the art and the left and right sides of a card, in pixels
+const ART_LEFT: u32 = 8;
+
+/// The distance between the art and the bottom of a card, in pixels
+const ART_BOTTOM: u32 = 78;
+
+/// The height of the art of a card, in pixels
+const ART_HEIGHT: u32 = 68;
+
+/// The height of the name of a card, in OpenGL coordinates of the card
+const NAME_HEIGHT: f32 = 0.1;
+
+/// The height of the type line and the description of a card, in OpenGL coordinates of the card
+const TEXT_HEIGHT: f32 = 0.075;
+
+/// The height of the numbers in the cost gems and the attack and health boxes, in OpenGL coordinates of the card
+const NUMBER_HEIGHT: f32 = 0.15;
+
+/// Get the color of the gem of a resource type, and the color of the number on that gem
+fn resource_colors(resource: ResourceType) -> (Color, Color) {
+    match resource {
+        ResourceType::Red => ((0.8, 0.1, 0.1, 1.0), WHITE),
+        ResourceType::Blue => ((0.1, 0.3, 0.9, 1.0), WHITE),
+        ResourceType::White => ((0.9, 0.9, 0.75, 1.0), BLACK),
+        ResourceType::Black => ((0.15, 0.15, 0.15, 1.0), WHITE),
+    }
+}
+
+/// Fill a rectangle on a card texture with a color
+/// The position and size are in pixels, starting at the bottom left corner of the card
+fn fill_rect(
+    frame_buffer: &mut SimpleFrameBuffer,
+    left: u32,
+    bottom: u32,
+    width: u32,
+    height: u32,
+    color: Color,
+) {
+    frame_buffer.clear(
+        Some(&Rect {
+            left,
+            bottom,
+            width,
+            height,
+        }),
+        Some(color),
+        false,
+        None,
+        None,
+    );
+}
+
+/// Draw a line of text on a card texture, with the bottom left corner of the text at `position`
+/// The position and height are in OpenGL coordinates of the card, so -1 is the left or bottom and 1 is the right or top of the card
+fn draw_text(
+    frame_buffer: &mut SimpleFrameBuffer,
+    render_state: &RenderState,
+    text: &str,
+    position: Point,
+    height: f32,
+    color: Color,
+) {
+    let text = TextDisplay::new(render_state.text_system, render_state.font, text);
+    draw_text_display(frame_buffer, render_state, &text, position, height, color);
+}
+
+/// Draw text that was already laid out on a card texture. See `draw_text`
+fn draw_text_display(
+    frame_buffer: &mut SimpleFrameBuffer,
+    render_state: &RenderState,
+    text: &TextDisplay<&FontTexture>,
+    position: Point,
+    height: f32,
+    color: Color,
+) {
+    // correct the width for the aspect ratio of the card so the text doesn't get stretched
+    let width = height * CARD_HEIGHT / CARD_WIDTH;
+    let matrix = [
+        [width, 0.0, 0.0, 0.0],
+        [0.0, height, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [position.x, position.y, 0.0, 1.0],
+    ];
+    glium_text::draw(text, render_state.text_system, frame_buffer, matrix, color);
+}
+
+/// Draw a square with a number in the middle of it on a card texture
+/// `bottom_left` and `size` are in pixels, starting at the bottom left corner of the card
+fn draw_number_box(
+    frame_buffer: &mut SimpleFrameBuffer,
+    render_state: &RenderState,
+    bottom_left: (u32, u32),
+    size: u32,
+    number: u8,
+    color: Color,
+    text_color: Color,
+) {
+    let (left, bottom) = bottom_left;
+    fill_rect(frame_buffer, left, bottom, size, size, color);
+
+    // convert the center of the box from pixels to OpenGL coordinates of the card
+    let center = Point::new(
+        (left as f32 + size as f32 / 2.0) / CARD_WIDTH * 2.0 - 1.0,
+        (bottom as f32 + size as f32 / 2.0) / CARD_HEIGHT * 2.0 - 1.0,
+    );
+    let text = TextDisplay::new(
+        render_state.text_system,
+        render_state.font,
+        &number.to_string(),
+    );
+    let width = text.get_width() * NUMBER_HEIGHT * CARD_HEIGHT / CARD_WIDTH;
+    let position = Point::new(center.x - width / 2.0, center.y - NUMBER_HEIGHT / 3.0);
+    draw_text_display(
+        frame_buffer,
+        render_state,
+        &text,
+        position,
+        NUMBER_HEIGHT,
+        text_color,
+    );
+}
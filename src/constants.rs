@@ -12,3 +12,9 @@ pub const CARD_ON_FIELD_SPACING: f32 = 175.0;
 
 /// The speed that a card bounces back to it's position
 pub const BOUNCE_BACK_FACTOR: f32 = 0.005f32;
+
+/// The amount of seconds between two actions while a replay is playing
+pub const REPLAY_STEP_TIME: f32 = 1.0;
+
+/// The amount of seconds between two actions of the AI
+pub const AI_STEP_TIME: f32 = 0.5;
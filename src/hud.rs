@@ -0,0 +1,267 @@
+use crate::point::Point;
+use card_game::cards::ResourceType;
+use card_game::gamestate::{PlayerView, Side, SideView};
+use glium::{Frame, Rect, Surface};
+use glium_text::{FontTexture, TextDisplay, TextSystem};
+
+/// The height of a line of text in the HUD, relative to the height of the screen
+const LINE_HEIGHT: f32 = 0.04;
+
+/// The distance between two lines of text in the HUD, in OpenGL coordinates
+const LINE_SPACING: f32 = 0.06;
+
+/// The x position of the left side of the HUD, in OpenGL coordinates
+const LEFT: f32 = -0.98;
+
+/// How far the graveyard line sticks out to the right of `LEFT` when it's clicked, in OpenGL coordinates
+const GRAVEYARD_CLICK_WIDTH: f32 = 0.35;
+
+/// The line of the HUD that shows the graveyard of a side
+const GRAVEYARD_LINE: usize = 3;
+
+/// Shows the state of both heroes on top of the board
+/// The opponent's information is in the top left corner of the screen, the player's information in the bottom left corner
+/// Clicking on the graveyard line of either side opens a list of all the cards in that graveyard
+#[derive(Default)]
+pub struct Hud {
+    // The side whose graveyard is being shown, if any
+    viewing_graveyard: Option<Side>,
+}
+
+impl Hud {
+    /// Handle a click of the mouse at the given point on the screen
+    /// Clicking anywhere closes the graveyard viewer, clicking on a graveyard line opens it
+    /// Returns true if the HUD handled the click, which means the click should not go to the board
+    pub fn mouse_pressed_at(&mut self, point: &Point, screen_size: &Point) -> bool {
+        if self.viewing_graveyard.take().is_some() {
+            return true;
+        }
+        // Convert the point on the screen to OpenGL coordinates
+        let point = Point::new(
+            point.x / screen_size.x * 2.0 - 1.0,
+            1.0 - point.y / screen_size.y * 2.0,
+        );
+        for side in &[Side::Player, Side::Opponent] {
+            let line_y = Hud::line_y(*side, GRAVEYARD_LINE);
+            let min = Point::new(LEFT, line_y);
+            let max = Point::new(LEFT + GRAVEYARD_CLICK_WIDTH, line_y + LINE_SPACING);
+            if point.between(&min, &max) {
+                self.viewing_graveyard = Some(*side);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Close the graveyard viewer
+    /// Returns true if it was open
+    pub fn close_graveyard(&mut self) -> bool {
+        self.viewing_graveyard.take().is_some()
+    }
+
+    /// Get the y position of a line of the HUD in OpenGL coordinates
+    /// The lines of the opponent start at the top of the screen, the lines of the player end at the bottom of the screen
+    fn line_y(side: Side, line: usize) -> f32 {
+        match side {
+            Side::Opponent => 0.92 - line as f32 * LINE_SPACING,
+            Side::Player => -0.98 + (GRAVEYARD_LINE - line) as f32 * LINE_SPACING,
+        }
+    }
+
+    /// Draw the HUD of both sides, and the graveyard viewer if it's open
+    pub fn draw(
+        &self,
+        frame: &mut Frame,
+        text_system: &TextSystem,
+        font: &FontTexture,
+        screen_size: &Point,
+        view: &PlayerView,
+    ) {
+        for side in &[Side::Player, Side::Opponent] {
+            let player = view.player(*side);
+            let lines = [
+                format!("{}: {} health", player.name(), player.health()),
+                format!("Resources: {}", Hud::resources_text(&player)),
+                format!("Deck: {} cards", player.deck_size()),
+                format!(
+                    "Graveyard: {} cards (click to view)",
+                    player.graveyard().len()
+                ),
+            ];
+            for (index, line) in lines.iter().enumerate() {
+                let position = Point::new(LEFT, Hud::line_y(*side, index));
+                draw_text(
+                    frame,
+                    text_system,
+                    font,
+                    screen_size,
+                    line,
+                    position,
+                    LINE_HEIGHT,
+                );
+            }
+        }
+
+        if let Some(side) = self.viewing_graveyard {
+            self.draw_graveyard(frame, text_system, font, screen_size, view, side);
+        }
+    }
+
+    /// Describe the available and total amount of every resource that the player has, e.g. `Red 1/2, White 0/1`
+    fn resources_text(player: &SideView) -> String {
+        let resources: Vec<String> = ResourceType::ALL
+            .iter()
+            .filter_map(|resource| {
+                let total: u8 = player
+                    .resources()
+                    .iter()
+                    .filter(|(r, _)| r == resource)
+                    .map(|(_, amount)| *amount)
+                    .sum();
+                if total == 0 {
+                    return None;
+                }
+                Some(format!(
+                    "{:?} {}/{}",
+                    resource,
+                    player.available_resource(*resource),
+                    total
+                ))
+            })
+            .collect();
+        if resources.is_empty() {
+            "none".to_string()
+        } else {
+            resources.join(", ")
+        }
+    }
+
+    /// Draw a panel in the middle of the screen that lists the cards in the graveyard of the given side
+    /// Copies of the same card are shown on a single line, in the order that the first copy died
+    fn draw_graveyard(
+        &self,
+        frame: &mut Frame,
+        text_system: &TextSystem,
+        font: &FontTexture,
+        screen_size: &Point,
+        view: &PlayerView,
+        side: Side,
+    ) {
+        frame.clear(
+            Some(&Rect {
+                left: (screen_size.x / 4.0) as u32,
+                bottom: (screen_size.y / 8.0) as u32,
+                width: (screen_size.x / 2.0) as u32,
+                height: (screen_size.y * 3.0 / 4.0) as u32,
+            }),
+            Some((0.0, 0.0, 0.0, 1.0)),
+            false,
+            None,
+            None,
+        );
+
+        let title = match side {
+            Side::Player => "Your graveyard",
+            Side::Opponent => "The opponent's graveyard",
+        };
+        draw_centered_text(frame, text_system, font, screen_size, title, 0.65, 0.05);
+
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for instance in view.player(side).graveyard() {
+            let name = instance.card.name();
+            match counts.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name, 1)),
+            }
+        }
+        if counts.is_empty() {
+            draw_centered_text(
+                frame,
+                text_system,
+                font,
+                screen_size,
+                "Empty",
+                0.5,
+                LINE_HEIGHT,
+            );
+        }
+        for (index, (name, count)) in counts.iter().enumerate() {
+            let line = format!("{}x {}", count, name);
+            let position = Point::new(-0.4, 0.5 - index as f32 * LINE_SPACING);
+            draw_text(
+                frame,
+                text_system,
+                font,
+                screen_size,
+                &line,
+                position,
+                LINE_HEIGHT,
+            );
+        }
+
+        draw_centered_text(
+            frame,
+            text_system,
+            font,
+            screen_size,
+            "Click anywhere to close",
+            -0.65,
+            LINE_HEIGHT,
+        );
+    }
+}
+
+/// Draw a line of text with its bottom left corner at `position`
+/// `position` is in OpenGL coordinates (-1 is the left or bottom, 1 is the right or top of the screen)
+/// and `height` is the height of the text relative to the height of the screen
+pub fn draw_text(
+    frame: &mut Frame,
+    text_system: &TextSystem,
+    font: &FontTexture,
+    screen_size: &Point,
+    text: &str,
+    position: Point,
+    height: f32,
+) {
+    let text = TextDisplay::new(text_system, font, text);
+    draw_text_display(frame, text_system, &text, screen_size, position, height);
+}
+
+/// Draw a line of text horizontally centered on the screen
+/// `y` is the vertical position in OpenGL coordinates (-1 is the bottom, 1 is the top of the screen)
+/// and `height` is the height of the text relative to the height of the screen
+pub fn draw_centered_text(
+    frame: &mut Frame,
+    text_system: &TextSystem,
+    font: &FontTexture,
+    screen_size: &Point,
+    text: &str,
+    y: f32,
+    height: f32,
+) {
+    let text = TextDisplay::new(text_system, font, text);
+    // correct the width for the aspect ratio of the screen so the text doesn't get stretched
+    let width = height * screen_size.y / screen_size.x;
+    let position = Point::new(-text.get_width() * width / 2.0, y);
+    draw_text_display(frame, text_system, &text, screen_size, position, height);
+}
+
+/// Draw text that was already laid out, with its bottom left corner at `position`
+fn draw_text_display(
+    frame: &mut Frame,
+    text_system: &TextSystem,
+    text: &TextDisplay<&FontTexture>,
+    screen_size: &Point,
+    position: Point,
+    height: f32,
+) {
+    // correct the width for the aspect ratio of the screen so the text doesn't get stretched
+    let width = height * screen_size.y / screen_size.x;
+    let matrix = [
+        [width, 0.0, 0.0, 0.0],
+        [0.0, height, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [position.x, position.y, 0.0, 1.0],
+    ];
+    glium_text::draw(text, text_system, frame, matrix, (1.0, 1.0, 1.0, 1.0));
+}
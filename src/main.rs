@@ -1,26 +1,175 @@
 #![cfg_attr(not(debug_assertions), deny(dead_code))]
 
+mod art;
+mod board;
 mod card_wrapper;
-mod cards;
 mod constants;
-mod gamestate;
+mod hud;
 mod point;
 mod render_state;
-mod utils;
+mod replay_viewer;
 
-use cards::ResourceType;
-use gamestate::{GameState, Player};
+use art::ArtCache;
+use board::Board;
+use card_game::ai::{Ai, Difficulty};
+use card_game::args::get_argument;
+use card_game::cards::{CardDatabase, ResourceType};
+use card_game::deck::{Deck, DeckFormat, DeckValidator};
+use card_game::gamestate::{Action, GameResult, GameState, Player, PlayerView, Side, Target};
+use card_game::network::{self, Lockstep, NetworkError, PlayerInfo, ServerSession, Spectators};
+use card_game::puzzle::{self, Puzzle, PuzzleStatus};
+use card_game::replay::Replay;
+use card_game::save::SavedGame;
+use constants::AI_STEP_TIME;
 use glium::glutin::{
     dpi::LogicalSize, ElementState, Event, EventsLoop, KeyboardInput, MouseButton, VirtualKeyCode,
     WindowBuilder, WindowEvent,
 };
 use glium::{Display, Program, Surface};
 use glium_text::{FontTexture, TextSystem};
+use hud::{draw_centered_text, Hud};
 use point::Point;
 use render_state::RenderState;
-use std::{fs::File, time::Instant};
+use replay_viewer::ReplayViewer;
+use std::{
+    fs,
+    fs::File,
+    net::TcpListener,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// The file that all the cards of the game are loaded from
+const CARD_DATABASE_FILE: &str = "assets/cards.ron";
+
+/// The deck that players play with when no other deck is given
+const DEFAULT_DECK_FILE: &str = "assets/decks/starter.deck";
+
+/// The file that the replay of the last finished game is saved to
+const REPLAY_FILE: &str = "replay.ron";
+
+/// The file that the game is saved to with F5, and loaded from with F9
+const SAVE_FILE: &str = "save.ron";
 
 fn main() {
+    let database = match CardDatabase::load(CARD_DATABASE_FILE) {
+        Ok(database) => database,
+        Err(e) => {
+            println!("Could not load {}: {}", CARD_DATABASE_FILE, e);
+            return;
+        }
+    };
+
+    let args: Vec<String> = std::env::args().collect();
+    let arguments = match Arguments::parse(&args) {
+        Ok(arguments) => arguments,
+        Err(e) => {
+            println!("{}", e);
+            return;
+        }
+    };
+
+    if let Some(directory) = arguments.verify_puzzles {
+        if !verify_puzzles(&database, directory) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut replay_viewer = None;
+    let mut puzzle = None;
+    let mut network = None;
+    let mut spectators = None;
+    let mut game_state = match (
+        arguments.replay_file,
+        arguments.puzzle_file,
+        arguments.load_file,
+    ) {
+        (Some(file), _, _) => {
+            let viewer = match Replay::load(file) {
+                Ok(replay) => ReplayViewer::new(replay, database.clone()),
+                Err(e) => {
+                    println!("Could not load replay {:?}: {}", file, e);
+                    return;
+                }
+            };
+            let game_state = match viewer.create_game() {
+                Ok(game_state) => game_state,
+                Err(e) => {
+                    println!("Could not play the replay: {}", e);
+                    return;
+                }
+            };
+            replay_viewer = Some(viewer);
+            game_state
+        }
+        (None, Some(file), _) => {
+            let loaded = match Puzzle::load(file) {
+                Ok(puzzle) => puzzle,
+                Err(e) => {
+                    println!("Could not load puzzle {:?}: {}", file, e);
+                    return;
+                }
+            };
+            let game_state = match loaded.create_game(&database) {
+                Ok(game_state) => game_state,
+                Err(e) => {
+                    println!("Could not start puzzle {:?}: {}", file, e);
+                    return;
+                }
+            };
+            println!("{}: {}", loaded.name, loaded.description);
+            puzzle = Some(loaded);
+            game_state
+        }
+        (None, None, Some(file)) => match load_game(&database, file) {
+            Ok(game_state) => {
+                println!("Loaded the game from {}", file);
+                game_state
+            }
+            Err(e) => {
+                println!("{}", e);
+                return;
+            }
+        },
+        (None, None, None)
+            if arguments.host.is_some()
+                || arguments.join.is_some()
+                || arguments.server.is_some()
+                || arguments.spectate.is_some() =>
+        {
+            match new_network_game(&database, &arguments) {
+                Ok((network_game, game_state, hosted_spectators)) => {
+                    network = Some(network_game);
+                    spectators = hosted_spectators;
+                    game_state
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    return;
+                }
+            }
+        }
+        (None, None, None) => match new_game(&database, &arguments) {
+            Ok(game_state) => game_state,
+            Err(e) => {
+                println!("{}", e);
+                return;
+            }
+        },
+    };
+
+    // In a network game the other player plays the opponent's side, so the AI never plays and the game can't be restarted
+    // A server only sends the part of the game that we're allowed to see, so a replay can't be made of those games
+    // Spectators only watch, so they can't take any actions
+    let network_game = network.is_some();
+    let spectating = matches!(network, Some(NetworkGame::Spectate(_)));
+    let server_game = matches!(
+        network,
+        Some(NetworkGame::Server(_)) | Some(NetworkGame::Spectate(_))
+    );
+    let mut network_error: Option<String> = None;
+
     // Default starting screen size. Will be updated with a resize event only
     let mut screen_size = Point::new(1280.0, 960.0);
 
@@ -45,46 +194,46 @@ fn main() {
     .unwrap();
 
     let mut last_frame_time = Instant::now();
-    let mut game_state = GameState::new(Player::new("Trangar"), Player::new("ubsan"));
+    let mut board = Board::default();
+    let mut hud = Hud::default();
+    let mut art_cache = ArtCache::default();
     let mut mouse_position = Point::zero();
+    board.sync(board_view(&game_state, spectating), &screen_size);
 
-    // Fill a deck with 60 cards, 15 of each type
-    for _ in 0..15 {
-        game_state
-            .player
-            .original_deck
-            .push(Box::new(cards::LightElemental { health: 10 }));
-        game_state
-            .player
-            .original_deck
-            .push(Box::new(cards::BuffCard {}));
-        game_state
-            .player
-            .original_deck
-            .push(Box::new(cards::GenericMinion {
-                name: String::from("Generic minion"),
-                attack: 5,
-                health: 5,
-                cost: vec![(ResourceType::Red, 3)],
-            }));
-        game_state
-            .player
-            .original_deck
-            .push(Box::new(cards::DamageSpellCard {}));
-    }
-
-    game_state.player.reset_deck();
-    for _ in 0..5 {
-        game_state.player.draw_card();
-    }
+    let ai = Ai::new(Side::Opponent, arguments.difficulty);
+    let mut ai_timer = 0.0;
 
-    game_state.update_card_origins(&screen_size);
+    // The hint for the puzzle, together with the number of actions that were taken when the hint was asked for
+    // The hint is only shown until the next action is taken
+    let mut puzzle_hint: Option<(usize, String)> = None;
 
+    let mut window_title = String::new();
+    let mut game_over = false;
     let mut running = true;
     while running {
         events_loop.poll_events(|event| {
             if let Event::WindowEvent { event, .. } = event {
                 match event {
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::Escape),
+                                ..
+                            },
+                        ..
+                    } if board.targeting_card.is_some() => {
+                        board.cancel_targeting(&screen_size);
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::Escape),
+                                ..
+                            },
+                        ..
+                    } if hud.close_graveyard() => {}
                     #[cfg(debug_assertions)]
                     WindowEvent::KeyboardInput {
                         input:
@@ -97,26 +246,197 @@ fn main() {
                     } => {
                         running = false;
                     }
+                    WindowEvent::KeyboardInput { .. } if spectating => {}
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } if replay_viewer.is_some() => {
+                        // While watching a replay, the keys control the playback instead of the game
+                        if let Some(ref mut viewer) = replay_viewer {
+                            let changed = match key {
+                                VirtualKeyCode::Space => {
+                                    viewer.toggle_playing();
+                                    false
+                                }
+                                VirtualKeyCode::Right => viewer.step_forward(&mut game_state),
+                                VirtualKeyCode::Left => {
+                                    let step = viewer.step().saturating_sub(1);
+                                    viewer.seek(step, &mut game_state)
+                                }
+                                VirtualKeyCode::Home => viewer.seek(0, &mut game_state),
+                                VirtualKeyCode::End => viewer.seek(usize::MAX, &mut game_state),
+                                _ => false,
+                            };
+                            if changed {
+                                board.sync(board_view(&game_state, spectating), &screen_size);
+                            }
+                        }
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::Return),
+                                ..
+                            },
+                        ..
+                    } => {
+                        board.reset_interaction();
+                        board.apply(&mut game_state, Action::EndTurn, &screen_size);
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::Space),
+                                ..
+                            },
+                        ..
+                    } => {
+                        board.reset_interaction();
+                        board.apply(&mut game_state, Action::AdvancePhase, &screen_size);
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::R),
+                                ..
+                            },
+                        ..
+                    } if (game_state.result().is_some() && !network_game) || puzzle.is_some() => {
+                        // Restart the game with the same decks, or start the puzzle over
+                        board.reset_interaction();
+                        match puzzle {
+                            Some(ref puzzle) => match puzzle.create_game(&database) {
+                                Ok(new_state) => game_state = new_state,
+                                Err(e) => println!("Could not restart the puzzle: {}", e),
+                            },
+                            None => {
+                                if let Err(e) = game_state.start_game() {
+                                    println!("Could not restart the game: {}", e);
+                                }
+                            }
+                        }
+                        board.sync(board_view(&game_state, spectating), &screen_size);
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::H),
+                                ..
+                            },
+                        ..
+                    } if puzzle.is_some() => {
+                        // Ask the solver for the next step towards solving the puzzle
+                        let hint = match puzzle::solve(&game_state) {
+                            Some(solution) if !solution.is_empty() => {
+                                format!("Hint: {}", describe_action(&game_state, solution[0]))
+                            }
+                            Some(_) => "The puzzle is already solved".to_string(),
+                            None => "The puzzle can't be solved anymore, press R to try again"
+                                .to_string(),
+                        };
+                        println!("{}", hint);
+                        puzzle_hint = Some((game_state.history().len(), hint));
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::F5),
+                                ..
+                            },
+                        ..
+                    } if !network_game && replay_viewer.is_none() && puzzle.is_none() => {
+                        match SavedGame::new(&game_state).save(SAVE_FILE) {
+                            Ok(()) => println!("Saved the game to {}", SAVE_FILE),
+                            Err(e) => println!("Could not save the game: {}", e),
+                        }
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::F9),
+                                ..
+                            },
+                        ..
+                    } if !network_game && replay_viewer.is_none() && puzzle.is_none() => {
+                        // Continue the game that was saved last, and throw away the current game
+                        match load_game(&database, SAVE_FILE) {
+                            Ok(loaded) => {
+                                println!("Loaded the game from {}", SAVE_FILE);
+                                game_over = loaded.result().is_some();
+                                game_state = loaded;
+                                board.reset_interaction();
+                                board.sync(board_view(&game_state, spectating), &screen_size);
+                            }
+                            Err(e) => println!("{}", e),
+                        }
+                    }
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } => {
+                        // The number keys pick the resource that gets added to the pool at the start of the turn
+                        let resource = match key {
+                            VirtualKeyCode::Key1 => Some(ResourceType::Red),
+                            VirtualKeyCode::Key2 => Some(ResourceType::Blue),
+                            VirtualKeyCode::Key3 => Some(ResourceType::White),
+                            VirtualKeyCode::Key4 => Some(ResourceType::Black),
+                            _ => None,
+                        };
+                        if let Some(resource) = resource {
+                            board.apply(
+                                &mut game_state,
+                                Action::SelectResource(resource),
+                                &screen_size,
+                            );
+                        }
+                    }
                     WindowEvent::CursorMoved { position, .. } => {
                         mouse_position = (position.x, position.y).into();
-                        game_state.mouse_moved_to(&mouse_position);
+                        board.mouse_moved_to(&mouse_position);
                     }
                     WindowEvent::Resized(new_size) => {
                         screen_size = (new_size.width, new_size.height).into();
-                        game_state.update_card_origins(&screen_size);
+                        board.update_card_origins(&screen_size);
                     }
                     WindowEvent::MouseInput {
                         state: ElementState::Pressed,
                         button: MouseButton::Left,
                         ..
                     } => {
-                        game_state.mouse_pressed_at(&mouse_position);
+                        // Clicks on the HUD don't reach the board
+                        let clicked_hud = hud.mouse_pressed_at(&mouse_position, &screen_size);
+                        if !clicked_hud && replay_viewer.is_none() && !spectating {
+                            board.mouse_pressed_at(&mut game_state, &mouse_position, &screen_size);
+                        }
                     }
                     WindowEvent::MouseInput {
-                        state: ElementState::Released,
+                        state: ElementState::Pressed,
+                        button: MouseButton::Right,
                         ..
                     } => {
-                        game_state.mouse_released(&screen_size);
+                        board.cancel_targeting(&screen_size);
+                    }
+                    WindowEvent::MouseInput {
+                        state: ElementState::Released,
+                        ..
+                    } if replay_viewer.is_none() && !spectating => {
+                        board.mouse_released(&mut game_state, &screen_size);
                     }
                     WindowEvent::CloseRequested => running = false,
                     _ => {}
@@ -124,17 +444,105 @@ fn main() {
             }
         });
 
+        // Show whose turn it is in the title bar, and only update it when it actually changes
+        let view = game_state.view(Side::Player);
+        let active_player = view.player(view.turn().active);
+        let resources = active_player
+            .resources()
+            .iter()
+            .map(|(resource, total)| {
+                format!(
+                    "{:?} {}/{}",
+                    resource,
+                    active_player.available_resource(*resource),
+                    total
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut title = format!(
+            "Turn {} - {} ({} health) - {:?} - resources: {}",
+            view.turn().number,
+            active_player.name(),
+            active_player.health(),
+            view.turn().phase,
+            resources
+        );
+        if let Some(ref viewer) = replay_viewer {
+            title = format!("{} - {}", title, viewer.status());
+        }
+        if let Some(ref puzzle) = puzzle {
+            title = format!("{} - Puzzle: {}", title, puzzle.name);
+        }
+        if spectating {
+            title = format!("{} - Spectating", title);
+        }
+        if let Some(ref error) = network_error {
+            title = format!("{} - {}", title, error);
+        }
+        if title != window_title {
+            display.gl_window().window().set_title(&title);
+            window_title = title;
+        }
+
         // Calculate the time between now and the previous frame time
         let elapsed = last_frame_time.elapsed().as_secs_f32();
         last_frame_time = Instant::now();
 
-        // TODO: Make this more efficient so we don't have to update all lists one by one
-        // Maybe use Vec.chain
-        for card in &mut game_state.player.hand {
-            card.update(elapsed);
+        if let Some(ref mut viewer) = replay_viewer {
+            if viewer.update(elapsed, &mut game_state) {
+                board.sync(board_view(&game_state, spectating), &screen_size);
+            }
+        } else if puzzle.is_none() {
+            // Send our actions to the other player or the server, and get the actions of the other player
+            // If the connection is lost or the games don't match anymore, the game stops until the window is closed
+            // Once the game is over, the other side is allowed to leave
+            if let Some(ref mut network_game) = network {
+                match network_game.update(&mut game_state) {
+                    Ok(changed) => {
+                        if changed {
+                            board.sync(board_view(&game_state, spectating), &screen_size);
+                        }
+                    }
+                    Err(e) => {
+                        if game_state.result().is_none() {
+                            println!("{}", e);
+                            network_error = Some(e.to_string());
+                        }
+                        network = None;
+                    }
+                }
+            }
+
+            // Spectators of our game see what happened after the delay, even after the other player left
+            if let Some(ref mut spectators) = spectators {
+                spectators.record_changes(&game_state);
+                spectators.update();
+            }
+
+            // Let the AI take its actions one at a time, so the player can follow what it's doing
+            if !network_game && game_state.turn.active == ai.side {
+                ai_timer += elapsed;
+                if ai_timer >= AI_STEP_TIME {
+                    ai_timer = 0.0;
+                    if let Some(action) = ai.choose_action(game_state.view(ai.side)) {
+                        board.apply(&mut game_state, action, &screen_size);
+                    }
+                }
+            }
+
+            // Save the replay of every game that we play, as soon as it's over
+            let is_over = game_state.result().is_some();
+            if is_over && !game_over && !server_game {
+                match Replay::new(&game_state).save(REPLAY_FILE) {
+                    Ok(()) => println!("Saved the replay to {}", REPLAY_FILE),
+                    Err(e) => println!("Could not save the replay: {}", e),
+                }
+            }
+            game_over = is_over;
         }
 
-        for card in &mut game_state.player.field {
+        for card in board.cards_mut() {
             card.update(elapsed);
         }
 
@@ -154,25 +562,413 @@ fn main() {
                 indices: &indices,
                 text_system: &text_system,
                 font: &font,
+                art: &mut art_cache,
             };
 
-            for card in &mut game_state.player.hand {
-                card.draw(&mut render_state);
-            }
-            for card in &mut game_state.player.field {
+            for card in board.cards_mut() {
                 card.draw(&mut render_state);
             }
 
             // If we're dragging a card, draw it again so it's always on the top
             // This does mean we're drawing it twice
             // TODO: see if the check of a card is being drawn is faster than drawing it twice
-            if let Some(reference) = game_state.dragging_card {
-                if let Some(ref mut card) = game_state.get_card_mut(&reference) {
+            if let Some(reference) = board.dragging_card {
+                if let Some(ref mut card) = board.get_card_mut(&reference) {
                     card.draw(&mut render_state);
                 }
             }
         }
 
+        hud.draw(
+            &mut frame,
+            &text_system,
+            &font,
+            &screen_size,
+            &game_state.view(Side::Player),
+        );
+
+        if let Some((step, ref hint)) = puzzle_hint {
+            if step == game_state.history().len() {
+                draw_centered_text(
+                    &mut frame,
+                    &text_system,
+                    &font,
+                    &screen_size,
+                    hint,
+                    -0.9,
+                    0.05,
+                );
+            }
+        }
+
+        // Once the game or the puzzle is over, show the result on top of the board
+        let puzzle_status = puzzle.as_ref().map(|_| puzzle::status(&game_state));
+        if let Some(status) = puzzle_status.filter(|status| *status != PuzzleStatus::Unsolved) {
+            let message = match status {
+                PuzzleStatus::Solved => "Puzzle solved!",
+                _ => "Puzzle failed!",
+            };
+            draw_centered_text(
+                &mut frame,
+                &text_system,
+                &font,
+                &screen_size,
+                message,
+                0.1,
+                0.1,
+            );
+            draw_centered_text(
+                &mut frame,
+                &text_system,
+                &font,
+                &screen_size,
+                "Press R to try again",
+                -0.1,
+                0.05,
+            );
+        } else if let Some(result) = game_state.result().filter(|_| puzzle.is_none()) {
+            let message = match result {
+                GameResult::Winner(side) if spectating => {
+                    format!("{} won!", game_state.player_at(side).name)
+                }
+                GameResult::Winner(Side::Player) => "You won!".to_string(),
+                GameResult::Winner(Side::Opponent) => "You lost!".to_string(),
+                GameResult::Draw => "It's a draw!".to_string(),
+            };
+            let hint = if replay_viewer.is_some() {
+                "Press Home to watch the replay again"
+            } else if network_game {
+                "Close the window to leave the game"
+            } else {
+                "Press R to restart"
+            };
+            draw_centered_text(
+                &mut frame,
+                &text_system,
+                &font,
+                &screen_size,
+                &message,
+                0.1,
+                0.1,
+            );
+            draw_centered_text(
+                &mut frame,
+                &text_system,
+                &font,
+                &screen_size,
+                hint,
+                -0.1,
+                0.05,
+            );
+        }
+
+        if let Some(ref error) = network_error {
+            draw_centered_text(
+                &mut frame,
+                &text_system,
+                &font,
+                &screen_size,
+                error,
+                -0.3,
+                0.05,
+            );
+        }
+
         frame.finish().unwrap();
     }
 }
+
+/// The options that can be given to the game on the command line
+struct Arguments<'a> {
+    // `--replay <file>` plays back the replay in the file instead of starting a new game
+    replay_file: Option<&'a str>,
+
+    // `--puzzle <file>` starts the puzzle in the file instead of a new game
+    puzzle_file: Option<&'a str>,
+
+    // `--load <file>` continues the game that was saved to the file
+    load_file: Option<&'a str>,
+
+    // `--verify-puzzles <directory>` checks that every puzzle in the directory can be solved, without opening a window
+    verify_puzzles: Option<&'a str>,
+
+    // `--deck <file>` and `--opponent-deck <file>` pick the decks that both players play with
+    deck_file: &'a str,
+    opponent_deck_file: &'a str,
+
+    // `--format <file>` picks the rules that both decks have to follow
+    format_file: Option<&'a str>,
+
+    // `--difficulty <easy|normal|hard|expert>` picks how well the opponent plays
+    difficulty: Difficulty,
+
+    // `--host <address>` waits for another player to join on the address, `--join <address>` joins their game
+    host: Option<&'a str>,
+    join: Option<&'a str>,
+
+    // `--server <address>` plays a game on the server at the address
+    server: Option<&'a str>,
+
+    // `--spectate <address>` watches the game that is hosted or played on a server at the address
+    // `--game <id>` picks the game to watch on a server, otherwise the newest game is picked
+    spectate: Option<&'a str>,
+    game: Option<u32>,
+
+    // `--spectator-delay <seconds>` holds back what spectators of a hosted game see
+    spectator_delay: Duration,
+
+    // `--name <name>` is the name that the other player sees in a network game
+    name: &'a str,
+}
+
+impl<'a> Arguments<'a> {
+    /// Parse the given command line arguments
+    /// Returns an error if an argument is given without a value
+    fn parse(args: &'a [String]) -> Result<Arguments<'a>, String> {
+        Ok(Arguments {
+            replay_file: get_argument(args, "--replay")?,
+            puzzle_file: get_argument(args, "--puzzle")?,
+            load_file: get_argument(args, "--load")?,
+            verify_puzzles: get_argument(args, "--verify-puzzles")?,
+            deck_file: get_argument(args, "--deck")?.unwrap_or(DEFAULT_DECK_FILE),
+            opponent_deck_file: get_argument(args, "--opponent-deck")?.unwrap_or(DEFAULT_DECK_FILE),
+            format_file: get_argument(args, "--format")?,
+            difficulty: match get_argument(args, "--difficulty")? {
+                Some(difficulty) => difficulty.parse()?,
+                None => Difficulty::Normal,
+            },
+            host: get_argument(args, "--host")?,
+            join: get_argument(args, "--join")?,
+            server: get_argument(args, "--server")?,
+            spectate: get_argument(args, "--spectate")?,
+            game: match get_argument(args, "--game")? {
+                Some(id) => Some(
+                    id.parse()
+                        .map_err(|_| format!("Invalid game {:?}, expected a number", id))?,
+                ),
+                None => None,
+            },
+            spectator_delay: match get_argument(args, "--spectator-delay")? {
+                Some(seconds) => Duration::from_secs(seconds.parse().map_err(|_| {
+                    format!(
+                        "Invalid spectator delay {:?}, expected a number of seconds",
+                        seconds
+                    )
+                })?),
+                None => Duration::from_secs(0),
+            },
+            name: get_argument(args, "--name")?.unwrap_or("Player"),
+        })
+    }
+}
+
+/// Create a new game where both players play with the decks from the given deck files
+/// Both decks are checked against the format, and the game is only created if both decks are valid
+fn new_game(database: &CardDatabase, arguments: &Arguments) -> Result<GameState, String> {
+    let format = match arguments.format_file {
+        Some(file) => DeckFormat::load(file)
+            .map_err(|e| format!("Could not load format {:?}: {}", file, e))?,
+        None => DeckFormat::default(),
+    };
+    let validator = DeckValidator::new(format);
+
+    let mut game_state = GameState::new(Player::new("Trangar"), Player::new("ubsan"));
+    for (side, file) in &[
+        (Side::Player, arguments.deck_file),
+        (Side::Opponent, arguments.opponent_deck_file),
+    ] {
+        let cards = Deck::load(file)
+            .and_then(|deck| deck.create_valid_cards(database, &validator))
+            .map_err(|e| format!("Could not load deck {:?}: {}", file, e))?;
+        game_state.player_at_mut(*side).original_deck = cards;
+    }
+    game_state.deck_validator = validator;
+    game_state.start_game().map_err(|e| e.to_string())?;
+    Ok(game_state)
+}
+
+/// A game that is played against another player over the network
+enum NetworkGame {
+    // We play directly against the other player, who hosted or joined our game
+    Lockstep(Lockstep),
+
+    // We play on a server, which owns the game
+    Server(ServerSession),
+
+    // We watch a game that someone else hosts or plays on a server
+    Spectate(ServerSession),
+}
+
+impl NetworkGame {
+    /// Send our actions, and update the game with what the other side did
+    /// Returns true if the game changed because of the other side
+    fn update(&mut self, game_state: &mut GameState) -> Result<bool, NetworkError> {
+        match self {
+            NetworkGame::Lockstep(lockstep) => lockstep
+                .update(game_state)
+                .map(|actions| !actions.is_empty()),
+            NetworkGame::Server(session) | NetworkGame::Spectate(session) => {
+                session.update(game_state).map(|events| events.is_some())
+            }
+        }
+    }
+}
+
+/// Get the game as it's shown on the board
+/// Spectators can't see the cards in either hand, everyone else sees the game from `Side::Player`
+fn board_view(game_state: &GameState, spectating: bool) -> PlayerView<'_> {
+    if spectating {
+        game_state.spectator_view()
+    } else {
+        game_state.view(Side::Player)
+    }
+}
+
+/// Load the game that was saved to the given file, see `SavedGame`
+fn load_game(database: &CardDatabase, file: &str) -> Result<GameState, String> {
+    SavedGame::load(file)
+        .and_then(|saved| saved.create_game(database))
+        .map_err(|e| format!("Could not load the game from {:?}: {}", file, e))
+}
+
+/// Host, join, play a network game on a server or watch a game, depending on the command line arguments
+/// The deck is checked against our format before it's sent, the host or server checks it against their format again
+/// When we host, everyone else that connects can watch our game through the returned spectators
+fn new_network_game(
+    database: &CardDatabase,
+    arguments: &Arguments,
+) -> Result<(NetworkGame, GameState, Option<Spectators>), String> {
+    if let Some(address) = arguments.spectate {
+        println!("Waiting for the game on {} to start", address);
+        return ServerSession::spectate(address, database, arguments.game)
+            .map(|(session, game_state)| (NetworkGame::Spectate(session), game_state, None))
+            .map_err(|e| e.to_string());
+    }
+
+    let format = match arguments.format_file {
+        Some(file) => DeckFormat::load(file)
+            .map_err(|e| format!("Could not load format {:?}: {}", file, e))?,
+        None => DeckFormat::default(),
+    };
+    let validator = DeckValidator::new(format.clone());
+    let file = arguments.deck_file;
+    let deck = Deck::load(file)
+        .and_then(|deck| deck.create_valid_cards(database, &validator).map(|_| deck))
+        .map_err(|e| format!("Could not load deck {:?}: {}", file, e))?;
+    let us = PlayerInfo::new(arguments.name, deck.cards);
+
+    let lockstep = |(connection, game_state): (network::Connection, GameState)| {
+        let lockstep = Lockstep::new(connection, &game_state);
+        (NetworkGame::Lockstep(lockstep), game_state, None)
+    };
+    let result = match (arguments.host, arguments.join, arguments.server) {
+        (Some(address), _, _) => {
+            let listener = TcpListener::bind(address)
+                .map_err(|e| format!("Could not host on {}: {}", address, e))?;
+            println!("Waiting for another player to join on {}", address);
+            network::host(&listener, database, &us, format).map(|(connection, game_state)| {
+                let (mut spectators, joining) = Spectators::new(arguments.spectator_delay);
+                spectators.record(&[], &game_state);
+                network::accept_spectators(listener, joining);
+                let lockstep = Lockstep::new(connection, &game_state);
+                (
+                    NetworkGame::Lockstep(lockstep),
+                    game_state,
+                    Some(spectators),
+                )
+            })
+        }
+        (None, Some(address), _) => {
+            println!("Joining the game on {}", address);
+            network::join(address, database, &us).map(lockstep)
+        }
+        (None, None, Some(address)) => {
+            println!("Waiting for an opponent on the server at {}", address);
+            ServerSession::join(address, database, &us)
+                .map(|(session, game_state)| (NetworkGame::Server(session), game_state, None))
+        }
+        (None, None, None) => return Err("No address to play on".to_string()),
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Check that every puzzle file in the given directory can be solved, and print the solutions
+/// Returns false if a puzzle can not be loaded or solved
+fn verify_puzzles(database: &CardDatabase, directory: &str) -> bool {
+    let mut files: Vec<_> = match fs::read_dir(directory) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+            .collect(),
+        Err(e) => {
+            println!("Could not read {:?}: {}", directory, e);
+            return false;
+        }
+    };
+    files.sort();
+
+    let mut all_solvable = true;
+    for file in &files {
+        match verify_puzzle(database, file) {
+            Ok(steps) => {
+                println!("{}: solvable", file.display());
+                for step in steps {
+                    println!("  {}", step);
+                }
+            }
+            Err(e) => {
+                println!("{}: {}", file.display(), e);
+                all_solvable = false;
+            }
+        }
+    }
+    all_solvable
+}
+
+/// Solve the puzzle in the given file, and describe the actions of the solution
+fn verify_puzzle(database: &CardDatabase, file: &Path) -> Result<Vec<String>, String> {
+    let mut game_state = Puzzle::load(file)
+        .and_then(|puzzle| puzzle.create_game(database))
+        .map_err(|e| e.to_string())?;
+    let solution = puzzle::solve(&game_state).ok_or("can not be solved")?;
+    let mut steps = Vec::new();
+    for action in solution {
+        steps.push(describe_action(&game_state, action));
+        game_state
+            .apply(Side::Player, action)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(steps)
+}
+
+/// Describe an action in words, as it would be taken in the given game
+fn describe_action(game_state: &GameState, action: Action) -> String {
+    let player = game_state.active_player();
+    let card_name = |cards: &[card_game::CardInstance], index: usize| {
+        cards
+            .get(index)
+            .map(|instance| instance.card.name().to_string())
+            .unwrap_or_else(|| "?".to_string())
+    };
+    let target_name = |target: Target| match target {
+        Target::Hero(side) if side == game_state.turn.active => "your hero".to_string(),
+        Target::Hero(_) => "the opponent's hero".to_string(),
+        Target::Minion(side, index) => card_name(&game_state.player_at(side).field, index),
+    };
+    match action {
+        Action::SelectResource(resource) => format!("Take a {:?} resource", resource),
+        Action::PlayCard { from, .. } => format!("Play {}", card_name(&player.hand, from)),
+        Action::CastSpell { card, target } => format!(
+            "Cast {} on {}",
+            card_name(&player.hand, card),
+            target_name(target)
+        ),
+        Action::Attack { attacker, target } => format!(
+            "Attack {} with {}",
+            target_name(target),
+            card_name(&player.field, attacker)
+        ),
+        Action::AdvancePhase => "Go to the next phase".to_string(),
+        Action::EndTurn => "End the turn".to_string(),
+    }
+}
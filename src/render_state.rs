@@ -1,5 +1,6 @@
 #![allow(deprecated)]
 
+use crate::art::ArtCache;
 use crate::constants::{CARD_HEIGHT, CARD_WIDTH};
 use crate::point::Point;
 use glium::backend::Facade;
@@ -17,6 +18,7 @@ pub struct RenderState<'a> {
     pub program: &'a Program,
     pub text_system: &'a TextSystem,
     pub font: &'a FontTexture,
+    pub art: &'a mut ArtCache,
 }
 
 impl<'a> RenderState<'a> {
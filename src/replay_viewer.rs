@@ -0,0 +1,121 @@
+use crate::constants::REPLAY_STEP_TIME;
+use card_game::cards::CardDatabase;
+use card_game::gamestate::GameState;
+use card_game::replay::{Replay, ReplayError};
+
+/// Plays back a recorded game
+/// The replay can be played and paused, stepped through one action at a time, or moved to any action in the replay
+pub struct ReplayViewer {
+    replay: Replay,
+
+    // The cards that the decks of the replay are made of
+    database: CardDatabase,
+
+    // The amount of actions of the replay that have been applied to the game state
+    step: usize,
+
+    // Is the replay currently playing, or is it paused?
+    playing: bool,
+
+    // The time since the last action was applied while playing
+    timer: f32,
+}
+
+impl ReplayViewer {
+    /// Create a new viewer for the given replay. The replay starts paused at the start of the game
+    pub fn new(replay: Replay, database: CardDatabase) -> ReplayViewer {
+        ReplayViewer {
+            replay,
+            database,
+            step: 0,
+            playing: false,
+            timer: 0.0,
+        }
+    }
+
+    /// Start a new game with the decks of the replay
+    pub fn create_game(&self) -> Result<GameState, ReplayError> {
+        self.replay.create_game(&self.database)
+    }
+
+    /// Start playing the replay if it's paused, or pause it if it's playing
+    pub fn toggle_playing(&mut self) {
+        self.playing = !self.playing;
+        self.timer = 0.0;
+    }
+
+    /// Update the replay with the time that passed since the last frame
+    /// While the replay is playing, this applies the next action every `REPLAY_STEP_TIME` seconds
+    /// Returns true if the game state changed
+    pub fn update(&mut self, elapsed: f32, game_state: &mut GameState) -> bool {
+        if !self.playing {
+            return false;
+        }
+        self.timer += elapsed;
+        if self.timer < REPLAY_STEP_TIME {
+            return false;
+        }
+        self.timer -= REPLAY_STEP_TIME;
+        let changed = self.step_forward(game_state);
+        if !changed {
+            // we reached the end of the replay
+            self.playing = false;
+        }
+        changed
+    }
+
+    /// Apply the next action of the replay to the game state
+    /// Returns true if the game state changed
+    pub fn step_forward(&mut self, game_state: &mut GameState) -> bool {
+        if self.step >= self.replay.actions.len() {
+            return false;
+        }
+        match self.replay.apply_step(game_state, self.step) {
+            Ok(()) => {
+                self.step += 1;
+                true
+            }
+            Err(e) => {
+                println!("Could not play the replay: {}", e);
+                self.playing = false;
+                false
+            }
+        }
+    }
+
+    /// Move the replay to right after the action at `step`, where 0 is the start of the game
+    /// The game gets replaced by a new game with the first `step` actions applied
+    /// Returns true if the game state changed
+    pub fn seek(&mut self, step: usize, game_state: &mut GameState) -> bool {
+        let step = step.min(self.replay.actions.len());
+        if step == self.step {
+            return false;
+        }
+        match self.replay.game_at(&self.database, step) {
+            Ok(new_state) => {
+                *game_state = new_state;
+                self.step = step;
+                true
+            }
+            Err(e) => {
+                println!("Could not play the replay: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Get the amount of actions of the replay that have been applied
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// A short text that describes where the replay is, e.g. "replay 3/20 (paused)"
+    pub fn status(&self) -> String {
+        format!(
+            "replay {}/{} ({})",
+            self.step,
+            self.replay.actions.len(),
+            if self.playing { "playing" } else { "paused" }
+        )
+    }
+}